@@ -0,0 +1,59 @@
+//! Generates the solution registry used by the `registry` feature.
+//! Every `src/bin/<year>-<day>.rs` is included as a module of the main binary, so that all solutions can run in one process.
+use std::{env, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+
+    let mut days: Vec<(u16, u8, String)> = vec![];
+
+    if env::var_os("CARGO_FEATURE_REGISTRY").is_some() {
+        let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+        for entry in fs::read_dir(bin_dir).into_iter().flatten().flatten() {
+            let path = entry.path();
+
+            let Some(stem) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+
+            if path.extension().is_none_or(|ext| ext != "rs") {
+                continue;
+            }
+
+            if let Some((year, day)) = stem.split_once('-') {
+                if let (Ok(year), Ok(day)) = (year.parse::<u16>(), day.parse::<u8>()) {
+                    days.push((year, day, path.display().to_string()));
+                }
+            }
+        }
+    }
+
+    days.sort_unstable();
+
+    let mut registry = String::new();
+
+    for (year, day, path) in &days {
+        registry.push_str(&format!(
+            "#[path = {path:?}]\n#[allow(dead_code)]\nmod day_{year}_{day:02};\n\n"
+        ));
+    }
+
+    registry.push_str(
+        "/// All solutions, sorted by year and day.\npub fn solutions() -> Vec<advent_of_code::template::runner::Solution> {\n    vec![\n",
+    );
+
+    for (year, day, _) in &days {
+        registry.push_str(&format!(
+            "        advent_of_code::template::runner::Solution {{\n            year: advent_of_code::template::Year::new({year}).unwrap(),\n            day: advent_of_code::day!({day}),\n            run: day_{year}_{day:02}::__run,\n        }},\n"
+        ));
+    }
+
+    registry.push_str("    ]\n}\n");
+
+    fs::write(Path::new(&out_dir).join("registry.rs"), registry).unwrap();
+}
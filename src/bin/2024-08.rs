@@ -156,13 +156,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(14));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(34));
     }
 
@@ -280,7 +280,7 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let result = parse_input(&advent_of_code::template::read_file("examples", DAY));
+        let result = parse_input(&advent_of_code::template::read_file("examples", YEAR, DAY));
 
         assert_eq!(result.n_cols, 12);
         assert_eq!(result.n_rows, 12);
@@ -64,7 +64,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(55312));
     }
 
@@ -76,7 +76,7 @@ mod tests {
 
     #[test]
     fn test_input() {
-        let (_, result) = parse_input(&advent_of_code::template::read_file("examples", DAY))
+        let (_, result) = parse_input(&advent_of_code::template::read_file("examples", YEAR, DAY))
             .expect("expected no error");
 
         assert_eq!(result, vec![125, 17]);
@@ -100,7 +100,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(65601038650482));
     }
 }
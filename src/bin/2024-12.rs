@@ -137,19 +137,19 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(140));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(80));
     }
 
     #[test]
     fn test_parse_input() {
-        let input = &advent_of_code::template::read_file("examples", DAY);
+        let input = &advent_of_code::template::read_file("examples", YEAR, DAY);
         let grid = parse_input(input);
 
         let expected = HashMap::from([
@@ -176,7 +176,7 @@ mod tests {
 
     #[test]
     fn test_fence_length() {
-        let input = &advent_of_code::template::read_file("examples", DAY);
+        let input = &advent_of_code::template::read_file("examples", YEAR, DAY);
         let grid = parse_input(input);
 
         let result = fence(&grid, (1, 0), 'B');
@@ -189,7 +189,7 @@ mod tests {
         // BBCD
         // BBCC
         // EEEC
-        let input = &advent_of_code::template::read_file("examples", DAY);
+        let input = &advent_of_code::template::read_file("examples", YEAR, DAY);
         let grid = parse_input(input);
 
         // assert_eq!(2, corner_count(&grid, &(0, 0), &'A'));
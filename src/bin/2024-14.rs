@@ -139,7 +139,11 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = solve(&advent_of_code::template::read_file("examples", DAY), 11, 7);
+        let result = solve(
+            &advent_of_code::template::read_file("examples", YEAR, DAY),
+            11,
+            7,
+        );
         assert_eq!(result, Some(12));
     }
 
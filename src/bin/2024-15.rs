@@ -427,7 +427,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(2028));
     }
 
@@ -476,7 +476,7 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
 
     #[test]
     fn test_step() {
-        let input = &advent_of_code::template::read_file("examples", DAY);
+        let input = &advent_of_code::template::read_file("examples", YEAR, DAY);
         let (mut grid, _, start) = parse_input(input);
 
         // ########
@@ -524,7 +524,7 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(1751));
     }
 
@@ -720,7 +720,7 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
 
     #[test]
     fn test_input() {
-        let input = &advent_of_code::template::read_file("examples", DAY);
+        let input = &advent_of_code::template::read_file("examples", YEAR, DAY);
         let (grid, movements, start) = parse_input(input);
 
         assert_eq!(start, (2, 2));
@@ -214,7 +214,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some("4,6,3,5,6,3,5,2,1,0".to_string()));
     }
 
@@ -254,7 +254,7 @@ Program: 0,3,5,4,3,0";
 
     #[test]
     fn test_parse() {
-        let (registers, _) = parse(&advent_of_code::template::read_file("examples", DAY));
+        let (registers, _) = parse(&advent_of_code::template::read_file("examples", YEAR, DAY));
 
         assert_eq!(
             registers,
@@ -165,7 +165,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let input = &advent_of_code::template::read_file("examples", DAY);
+        let input = &advent_of_code::template::read_file("examples", YEAR, DAY);
         let result = solve_part_one(input, TEST_SIZE, NUM_BYTES);
 
         assert_eq!(result, Some(22));
@@ -174,7 +174,7 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = solve_part_two(
-            &advent_of_code::template::read_file("examples", DAY),
+            &advent_of_code::template::read_file("examples", YEAR, DAY),
             TEST_SIZE,
         );
         assert_eq!(result, Some(Vec2(6, 1)));
@@ -56,19 +56,20 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, Some(3));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", YEAR, DAY));
         assert_eq!(result, None);
     }
 
     #[test]
     fn test_parse_input() {
-        let (keys, locks) = parse_input(&advent_of_code::template::read_file("examples", DAY));
+        let (keys, locks) =
+            parse_input(&advent_of_code::template::read_file("examples", YEAR, DAY));
 
         assert_eq!(
             vec![
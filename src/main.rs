@@ -1,40 +1,102 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, export, read, scaffold, solve, time, verify,
+};
 use args::{parse, AppArguments};
 
+use advent_of_code::template::runner::Solution;
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::{Day, Year};
 #[cfg(feature = "today")]
 use std::process;
 
+/// Compile-time registry of all solutions in `src/bin`, generated by `build.rs`.
+#[cfg(feature = "registry")]
+mod registry {
+    include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+}
+
+/// With the registry, solutions run in this binary, which counts their allocations if `alloc-metrics` is enabled.
+#[cfg(all(feature = "alloc-metrics", feature = "registry", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: advent_of_code::template::alloc::CountingAlloc =
+    advent_of_code::template::alloc::CountingAlloc;
+
+/// Returns the registry if the `registry` feature is enabled.
+/// Without it, solutions are run by invoking their binaries.
+fn get_registry() -> Option<Vec<Solution>> {
+    #[cfg(feature = "registry")]
+    return Some(registry::solutions());
+    #[cfg(not(feature = "registry"))]
+    None
+}
+
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::{
+        commands::export::{ExportOptions, Format},
+        commands::time::{CompareOptions, DEFAULT_THRESHOLD},
+        config::{BenchOverrides, Timeouts},
+        Day, Year,
+    };
     use std::process;
 
     pub enum AppArguments {
         Download {
+            year: Year,
             day: Day,
         },
         Read {
+            year: Year,
             day: Day,
+            part: Option<u8>,
+        },
+        Examples {
+            year: Year,
+            day: Day,
+            overwrite: bool,
         },
         Scaffold {
+            year: Year,
             day: Day,
+            template: Option<String>,
+            return_type: Option<String>,
             download: bool,
+            examples: bool,
             overwrite: bool,
         },
         Solve {
+            year: Year,
             day: Day,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            no_wait: bool,
+            examples: bool,
+            time: Option<BenchOverrides>,
+            timeouts: Timeouts,
         },
         All {
+            year: Year,
             release: bool,
+            jobs: usize,
+            timeouts: Timeouts,
+        },
+        Verify {
+            year: Year,
+            day: Option<Day>,
+            release: bool,
+            jobs: usize,
+            timeouts: Timeouts,
         },
         Time {
+            year: Year,
             all: bool,
             day: Option<Day>,
             store: bool,
+            label: Option<String>,
+            compare: Option<CompareOptions>,
+            export: Option<ExportOptions>,
+            bench: BenchOverrides,
+            timeouts: Timeouts,
         },
         #[cfg(feature = "today")]
         Today,
@@ -45,35 +107,144 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: parse_year(&mut args)?,
                 release: args.contains("--release"),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1_usize).max(1),
+                timeouts: Timeouts::parse(&mut args)?,
             },
+            Some("verify") => {
+                let year = parse_year(&mut args)?;
+
+                AppArguments::Verify {
+                    year,
+                    release: args.contains("--release"),
+                    jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1_usize).max(1),
+                    timeouts: Timeouts::parse(&mut args)?,
+                    day: parse_opt_day(&mut args, year)?,
+                }
+            }
             Some("time") => {
+                if args.contains("--jobs") {
+                    eprintln!("`time` does not support `--jobs`, parallel runs distort timings.");
+                    process::exit(1);
+                }
+
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let label = args.opt_value_from_str("--label")?;
+                let baseline: Option<String> = args.opt_value_from_str("--baseline")?;
+                let threshold = args.opt_value_from_str("--threshold")?;
+                let compare =
+                    (args.contains("--compare") || baseline.is_some()).then(|| CompareOptions {
+                        baseline,
+                        threshold: threshold.unwrap_or(DEFAULT_THRESHOLD),
+                    });
+                let export_format: Option<Format> = args.opt_value_from_str("--export")?;
+                let readme = args.contains("--readme");
+                let bench = BenchOverrides::parse(&mut args)?;
+                let timeouts = Timeouts::parse(&mut args)?;
+                let year = parse_year(&mut args)?;
+
+                let (day, export) = match export_format {
+                    Some(format) => {
+                        // exporting only reads stored timings, nothing is run or stored.
+                        if all
+                            || store
+                            || label.is_some()
+                            || compare.is_some()
+                            || threshold.is_some()
+                            || bench != BenchOverrides::default()
+                            || timeouts != Timeouts::default()
+                        {
+                            return Err("`--export` only reads stored timings and does not run solutions, store them with `cargo time --store` first.".into());
+                        }
+
+                        let (day, path) = parse_opt_day_and_path(&mut args, year)?;
+                        (
+                            day,
+                            Some(ExportOptions {
+                                format,
+                                path,
+                                readme,
+                            }),
+                        )
+                    }
+                    None if readme => {
+                        return Err("`--readme` requires `--export svg <path>`.".into());
+                    }
+                    None => (parse_opt_day(&mut args, year)?, None),
+                };
 
                 AppArguments::Time {
+                    year,
                     all,
-                    day: args.opt_free_from_str()?,
+                    day,
                     store,
+                    label,
+                    compare,
+                    export,
+                    bench,
+                    timeouts,
+                }
+            }
+            Some("download") => {
+                let year = parse_year(&mut args)?;
+                AppArguments::Download {
+                    year,
+                    day: parse_day(&mut args, year)?,
+                }
+            }
+            Some("read") => {
+                let year = parse_year(&mut args)?;
+                AppArguments::Read {
+                    year,
+                    day: parse_day(&mut args, year)?,
+                    part: parse_part(&mut args)?,
+                }
+            }
+            Some("examples") => {
+                let year = parse_year(&mut args)?;
+
+                AppArguments::Examples {
+                    year,
+                    day: parse_day(&mut args, year)?,
+                    overwrite: args.contains("--overwrite"),
+                }
+            }
+            Some("scaffold") => {
+                let year = parse_year(&mut args)?;
+
+                AppArguments::Scaffold {
+                    year,
+                    day: parse_day(&mut args, year)?,
+                    template: args.opt_value_from_str("--template")?,
+                    return_type: args.opt_value_from_str("--return-type")?,
+                    download: args.contains("--download"),
+                    examples: args.contains("--examples"),
+                    overwrite: args.contains("--overwrite"),
+                }
+            }
+            Some("solve") => {
+                let time = if args.contains("--time") {
+                    Some(BenchOverrides::parse(&mut args)?)
+                } else {
+                    None
+                };
+                let timeouts = Timeouts::parse(&mut args)?;
+                let year = parse_year(&mut args)?;
+
+                AppArguments::Solve {
+                    year,
+                    day: parse_day(&mut args, year)?,
+                    release: args.contains("--release"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    no_wait: args.contains("--no-wait"),
+                    examples: args.contains("--examples"),
+                    dhat: args.contains("--dhat"),
+                    time,
+                    timeouts,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
-            },
-            Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
-                overwrite: args.contains("--overwrite"),
-            },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -93,6 +264,74 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parse the `--year` option, defaulting to the `AOC_YEAR` environment variable.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str("--year")? {
+            Some(year) => Ok(year),
+            None => Year::from_env().ok_or_else(|| {
+                "no year specified, pass `--year <year>` or set the `AOC_YEAR` environment variable."
+                    .into()
+            }),
+        }
+    }
+
+    /// Parse the day argument and check that it is part of the event of `year`.
+    fn parse_day(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<Day, Box<dyn std::error::Error>> {
+        check_day(year, args.free_from_str()?)
+    }
+
+    /// Parse an optional day argument and check that it is part of the event of `year`.
+    fn parse_opt_day(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<Option<Day>, Box<dyn std::error::Error>> {
+        args.opt_free_from_str()?
+            .map(|day| check_day(year, day))
+            .transpose()
+    }
+
+    /// Parse an optional day argument followed by a path, i.e. `[<day>] <path>`.
+    fn parse_opt_day_and_path(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<(Option<Day>, String), Box<dyn std::error::Error>> {
+        let Some(first) = args.opt_free_from_str::<String>()? else {
+            return Err("`--export` expects a path, e.g. `--export csv timings.csv`.".into());
+        };
+
+        match args.opt_free_from_str::<String>()? {
+            Some(path) => Ok((Some(check_day(year, first.parse()?)?), path)),
+            None => Ok((None, first)),
+        }
+    }
+
+    /// Parse the optional `--part <1|2>` option.
+    fn parse_part(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<u8>, Box<dyn std::error::Error>> {
+        match args.opt_value_from_str("--part")? {
+            Some(part) if part != 1 && part != 2 => {
+                Err(format!("part {part} does not exist, expecting 1 or 2.").into())
+            }
+            part => Ok(part),
+        }
+    }
+
+    fn check_day(year: Year, day: Day) -> Result<Day, Box<dyn std::error::Error>> {
+        if year.has_day(day) {
+            Ok(day)
+        } else {
+            Err(format!(
+                "day {day} is not part of the {year} event, expecting a day between 1 and {}.",
+                year.last_day()
+            )
+            .into())
+        }
+    }
 }
 
 fn main() {
@@ -102,38 +341,112 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                year,
+                release,
+                jobs,
+                timeouts,
+            } => all::handle(year, release, jobs, timeouts, get_registry().as_deref()),
+            AppArguments::Verify {
+                year,
+                day,
+                release,
+                jobs,
+                timeouts,
+            } => verify::handle(
+                year,
+                day,
+                release,
+                jobs,
+                timeouts,
+                get_registry().as_deref(),
+            ),
+            AppArguments::Time {
+                year,
+                day,
+                all,
+                store,
+                label,
+                compare,
+                export,
+                bench,
+                timeouts,
+            } => match export {
+                Some(export) => export::handle(year, day, &export),
+                None => time::handle(
+                    year,
+                    day,
+                    all,
+                    store,
+                    label,
+                    compare.as_ref(),
+                    &bench,
+                    timeouts,
+                    get_registry().as_deref(),
+                ),
+            },
+            AppArguments::Download { year, day } => download::handle(year, day),
+            AppArguments::Read { year, day, part } => read::handle(year, day, part),
+            AppArguments::Examples {
+                year,
+                day,
+                overwrite,
+            } => examples::handle(year, day, overwrite),
             AppArguments::Scaffold {
+                year,
                 day,
+                template,
+                return_type,
                 download,
+                examples,
                 overwrite,
             } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
+                scaffold::handle(
+                    year,
+                    day,
+                    template.as_deref(),
+                    return_type.as_deref(),
+                    download,
+                    overwrite,
+                );
+                if examples {
+                    examples::handle(year, day, overwrite);
                 }
+                scaffold::update_tests(year, day);
             }
             AppArguments::Solve {
+                year,
+                day,
+                release,
+                dhat,
+                submit,
+                no_wait,
+                examples,
+                time,
+                timeouts,
+            } => solve::handle(
+                year,
                 day,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                no_wait,
+                examples,
+                time.as_ref(),
+                &timeouts,
+            ),
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                match (Year::today(), Day::today()) {
+                    (Some(year), Some(day)) => {
+                        scaffold::handle(year, day, None, None, true, false);
+                        scaffold::update_tests(year, day);
+                        read::handle(year, day, None)
                     }
-                    None => {
+                    _ => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run during the event in december. \
+                            Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
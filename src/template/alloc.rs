@@ -0,0 +1,276 @@
+/// Counts heap allocations of solution parts. Enabled with the `alloc-metrics` feature, which makes
+/// the `solution!` macro install [`CountingAlloc`] as the global allocator.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    collections::HashMap,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use tinyjson::JsonValue;
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// Allocations of the current thread. Counting per thread keeps e.g. the timeout watchdog out of the stats.
+struct Counters {
+    allocated: Cell<u64>,
+    allocations: Cell<u64>,
+    /// Signed, since a block can be freed by another thread than the one that allocated it.
+    live: Cell<i64>,
+    peak: Cell<i64>,
+}
+
+thread_local! {
+    // const-initialized without a destructor, so it can be used from within the allocator.
+    static COUNTERS: Counters = const {
+        Counters {
+            allocated: Cell::new(0),
+            allocations: Cell::new(0),
+            live: Cell::new(0),
+            peak: Cell::new(0),
+        }
+    };
+}
+
+/// Wraps the system allocator and counts allocated, live and peak bytes of every thread.
+pub struct CountingAlloc;
+
+impl CountingAlloc {
+    fn record_alloc(size: usize) {
+        if !INSTALLED.load(Ordering::Relaxed) {
+            INSTALLED.store(true, Ordering::Relaxed);
+        }
+
+        let _ = COUNTERS.try_with(|c| {
+            c.allocated.set(c.allocated.get() + size as u64);
+            c.allocations.set(c.allocations.get() + 1);
+            let live = c.live.get() + size as i64;
+            c.live.set(live);
+            c.peak.set(c.peak.get().max(live));
+        });
+    }
+
+    fn record_dealloc(size: usize) {
+        let _ = COUNTERS.try_with(|c| c.live.set(c.live.get() - size as i64));
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::record_dealloc(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // a reallocation counts as freeing the old block and allocating a new one.
+            Self::record_dealloc(layout.size());
+            Self::record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// Allocations of a single run of a solution part.
+/// Only allocations of the thread that ran the part are counted, not those of threads it spawned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    /// Total bytes allocated.
+    pub bytes: u64,
+    /// Number of allocations.
+    pub count: u64,
+    /// Highest number of bytes that were allocated at the same time, on top of what was allocated before the run.
+    pub peak_bytes: u64,
+}
+
+/// Run `func` and count its allocations. Returns `None` for the stats if [`CountingAlloc`] is not installed.
+/// Calls on different threads do not affect each other, nested calls leave the peak of the outer call intact.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    if !INSTALLED.load(Ordering::Relaxed) {
+        return (func(), None);
+    }
+
+    let (allocated, allocations, live, outer_peak) = COUNTERS.with(|c| {
+        let before = (
+            c.allocated.get(),
+            c.allocations.get(),
+            c.live.get(),
+            c.peak.get(),
+        );
+        c.peak.set(c.live.get());
+        before
+    });
+
+    let result = func();
+
+    let stats = COUNTERS.with(|c| {
+        let peak = c.peak.get();
+        c.peak.set(outer_peak.max(peak));
+
+        #[allow(clippy::cast_sign_loss)]
+        AllocStats {
+            bytes: c.allocated.get() - allocated,
+            count: c.allocations.get() - allocations,
+            peak_bytes: (peak - live).max(0) as u64,
+        }
+    });
+
+    (result, Some(stats))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&AllocStats> for JsonValue {
+    fn from(value: &AllocStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+            map.insert("count".into(), JsonValue::Number(value.count as f64));
+            map.insert(
+                "peak_bytes".into(),
+                JsonValue::Number(value.peak_bytes as f64),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected alloc to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .filter(|x| **x >= 0.0)
+                .map(|x| *x as u64)
+                .ok_or(format!("Expected alloc.{key} to be a positive number."))
+        };
+
+        Ok(AllocStats {
+            bytes: number("bytes")?,
+            count: number("count")?,
+            peak_bytes: number("peak_bytes")?,
+        })
+    }
+}
+
+/// Parse optional stats, e.g. of records that were written without the `alloc-metrics` feature.
+pub fn parse_alloc(value: Option<&JsonValue>) -> Result<Option<AllocStats>, String> {
+    match value {
+        Some(v) if !v.is_null() => AllocStats::try_from(v).map(Some),
+        _ => Ok(None),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::alloc::{GlobalAlloc, Layout};
+
+    use tinyjson::JsonValue;
+
+    use super::{measure, parse_alloc, AllocStats, CountingAlloc};
+
+    #[test]
+    fn roundtrips_stats() {
+        let stats = AllocStats {
+            bytes: 4096,
+            count: 12,
+            peak_bytes: 1024,
+        };
+        let json = JsonValue::from(&stats);
+        assert_eq!(parse_alloc(Some(&json)), Ok(Some(stats)));
+        assert_eq!(parse_alloc(Some(&JsonValue::Null)), Ok(None));
+        assert_eq!(parse_alloc(None), Ok(None));
+    }
+
+    #[test]
+    fn rejects_malformed_stats() {
+        let json: JsonValue = r#"{ "bytes": 1, "count": -1, "peak_bytes": 0 }"#.parse().unwrap();
+        assert!(parse_alloc(Some(&json)).is_err());
+    }
+
+    #[test]
+    fn counts_allocations() {
+        // the allocator is not installed in the test binary, it is called directly below.
+        let (result, stats) = measure(|| 3);
+        assert_eq!(result, 3);
+        assert_eq!(stats, None);
+
+        let layout = Layout::from_size_align(1024, 8).unwrap();
+        unsafe {
+            let ptr = CountingAlloc.alloc(layout);
+            CountingAlloc.dealloc(ptr, layout);
+        }
+
+        let (_, stats) = measure(|| unsafe {
+            let a = CountingAlloc.alloc(layout);
+            let b = CountingAlloc.alloc(layout);
+            CountingAlloc.dealloc(a, layout);
+            let b = CountingAlloc.realloc(b, layout, 2048);
+            CountingAlloc.dealloc(b, Layout::from_size_align(2048, 8).unwrap());
+        });
+        assert_eq!(
+            stats,
+            Some(AllocStats {
+                bytes: 4096,
+                count: 3,
+                peak_bytes: 2048,
+            })
+        );
+
+        // allocations of other threads are not counted, nested calls keep the outer peak.
+        let (_, stats) = measure(|| unsafe {
+            let a = CountingAlloc.alloc(layout);
+            CountingAlloc.dealloc(a, layout);
+
+            std::thread::spawn(move || {
+                let b = CountingAlloc.alloc(layout);
+                CountingAlloc.dealloc(b, layout);
+            })
+            .join()
+            .unwrap();
+
+            let (_, inner) = measure(|| {
+                let c = CountingAlloc.alloc(Layout::from_size_align(16, 8).unwrap());
+                CountingAlloc.dealloc(c, Layout::from_size_align(16, 8).unwrap());
+            });
+            assert_eq!(inner.map(|s| (s.count, s.peak_bytes)), Some((1, 16)));
+        });
+        assert_eq!(
+            stats,
+            Some(AllocStats {
+                bytes: 1024 + 16,
+                count: 2,
+                peak_bytes: 1024,
+            })
+        );
+    }
+}
@@ -0,0 +1,188 @@
+/// Accepted answers per day and part, stored in `data/<year>/answers.json`.
+///
+/// The file is filled when an answer is accepted on submission and can be edited by hand:
+/// ```json
+/// {
+///     "01": { "part_1": "2264607", "part_2": 19457120 },
+///     "02": { "part_1": "school" }
+/// }
+/// ```
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::{get_data_path, Day, Year};
+
+#[must_use]
+pub fn get_answers_path(year: Year) -> String {
+    get_data_path(year, "answers.json")
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    data: HashMap<Day, [Option<String>; 2]>,
+}
+
+impl Answers {
+    /// Rehydrate answers from the JSON file. If not present, returns empty answers.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        match fs::read_to_string(get_answers_path(year)) {
+            Ok(s) => Answers::try_from(s),
+            Err(_) => Ok(Answers::default()),
+        }
+    }
+
+    /// Dehydrate answers to the JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        fs::create_dir_all(get_data_path(year, ""))?;
+        let mut file = fs::File::create(get_answers_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// The accepted answer for a part (`1` or `2`), if known.
+    pub fn get(&self, day: Day, part: u8) -> Option<&str> {
+        let index = Self::index(part)?;
+        self.data.get(&day)?[index].as_deref()
+    }
+
+    pub fn set(&mut self, day: Day, part: u8, answer: &str) {
+        if let Some(index) = Self::index(part) {
+            self.data.entry(day).or_default()[index] = Some(answer.trim().to_string());
+        }
+    }
+
+    /// Days that have at least one known answer, in order.
+    pub fn days(&self) -> Vec<Day> {
+        let mut days: Vec<Day> = self
+            .data
+            .iter()
+            .filter(|(_, parts)| parts.iter().any(Option::is_some))
+            .map(|(day, _)| *day)
+            .collect();
+        days.sort_unstable();
+        days
+    }
+
+    fn index(part: u8) -> Option<usize> {
+        match part {
+            1 | 2 => Some(usize::from(part - 1)),
+            _ => None,
+        }
+    }
+}
+
+/// Record an accepted answer in the answers file.
+pub fn record(year: Year, day: Day, part: u8, answer: &str) -> Result<(), String> {
+    let mut answers = Answers::read_from_file(year)?;
+    answers.set(day, part, answer);
+    answers.store_file(year).map_err(|e| e.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        for (day, parts) in &value.data {
+            let mut day_map: HashMap<String, JsonValue> = HashMap::new();
+
+            for (i, answer) in parts.iter().enumerate() {
+                if let Some(answer) = answer {
+                    day_map.insert(format!("part_{}", i + 1), JsonValue::String(answer.clone()));
+                }
+            }
+
+            map.insert(day.to_string(), JsonValue::Object(day_map));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("answers are not a valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected answers to be an object.")?;
+
+        let mut data = HashMap::new();
+
+        for (key, value) in json {
+            let day = Day::from_str(key)
+                .map_err(|_| format!("expected answers key `{key}` to be a day."))?;
+
+            let day_json = value
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or(format!("expected answers.{key} to be an object."))?;
+
+            let answer = |part: &str| match day_json.get(part) {
+                None | Some(JsonValue::Null) => Ok(None),
+                Some(JsonValue::String(x)) => Ok(Some(x.trim().to_string())),
+                // answers are usually numbers, accept them unquoted when edited by hand.
+                Some(JsonValue::Number(x)) if x.fract() == 0.0 => Ok(Some(format!("{x:.0}"))),
+                Some(_) => Err(format!(
+                    "expected answers.{key}.{part} to be a string or an integer."
+                )),
+            };
+
+            data.insert(day, [answer("part_1")?, answer("part_2")?]);
+        }
+
+        Ok(Answers { data })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::Answers;
+    use crate::day;
+
+    #[test]
+    fn reads_hand_edited_answers() {
+        let answers = Answers::try_from(
+            r#"{
+                "01": { "part_1": "2264607", "part_2": 19457120 },
+                "02": { "part_1": " school " }
+            }"#
+            .to_string(),
+        )
+        .unwrap();
+
+        assert_eq!(answers.get(day!(1), 1), Some("2264607"));
+        assert_eq!(answers.get(day!(1), 2), Some("19457120"));
+        assert_eq!(answers.get(day!(2), 1), Some("school"));
+        assert_eq!(answers.get(day!(2), 2), None);
+        assert_eq!(answers.get(day!(3), 1), None);
+        assert_eq!(answers.days(), vec![day!(1), day!(2)]);
+    }
+
+    #[test]
+    fn roundtrips_answers() {
+        let mut answers = Answers::default();
+        answers.set(day!(17), 1, "4,6,3,5,6,3,5,2,1,0\n");
+        answers.set(day!(17), 2, "117440");
+        answers.set(day!(17), 3, "ignored");
+
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        let parsed = Answers::try_from(json).unwrap();
+
+        assert_eq!(parsed, answers);
+        assert_eq!(parsed.get(day!(17), 1), Some("4,6,3,5,6,3,5,2,1,0"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_fractional_answers() {
+        Answers::try_from(r#"{ "01": { "part_1": 1.5 } }"#.to_string()).unwrap();
+    }
+}
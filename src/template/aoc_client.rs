@@ -0,0 +1,413 @@
+/// Client for the advent of code website.
+/// Downloads inputs and puzzles and submits answers, authenticated with your session cookie.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::template::{answers, get_data_path, html, Day, Year};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Advent of code asks automated tools to identify themselves. Override with `AOC_USER_AGENT`, e.g. with your repository.
+const DEFAULT_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session cookie was found in the environment or the session file.
+    SessionNotFound,
+    /// The website rejected the session cookie, it probably expired.
+    Unauthorized,
+    /// The puzzle is not unlocked yet.
+    NotFound,
+    BadStatus(u16),
+    Transport(String),
+    /// The response of a submission could not be understood.
+    UnexpectedResponse(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set the `AOC_SESSION` environment variable or create the file \"~/.adventofcode.session\"."
+            ),
+            AocClientError::Unauthorized => {
+                write!(f, "the session cookie was rejected, it may have expired.")
+            }
+            AocClientError::NotFound => write!(f, "the puzzle is not available (yet)."),
+            AocClientError::BadStatus(status) => {
+                write!(f, "the server responded with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::UnexpectedResponse(text) => {
+                write!(f, "could not understand the response: {text}")
+            }
+            AocClientError::IO(e) => write!(f, "could not write file: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+/// The outcome of submitting an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, holds the remaining wait.
+    RateLimited(Duration),
+    /// The part was solved before, or part one is not solved yet.
+    AlreadySolved,
+}
+
+impl Display for SubmitOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "That's the right answer!"),
+            SubmitOutcome::Incorrect => write!(f, "That's not the right answer."),
+            SubmitOutcome::TooHigh => write!(f, "That's not the right answer, it is too high."),
+            SubmitOutcome::TooLow => write!(f, "That's not the right answer, it is too low."),
+            SubmitOutcome::RateLimited(wait) => write!(
+                f,
+                "You gave an answer too recently, wait {}s before submitting again.",
+                wait.as_secs()
+            ),
+            SubmitOutcome::AlreadySolved => write!(
+                f,
+                "This part is not open for submissions, did you already solve it?"
+            ),
+        }
+    }
+}
+
+impl SubmitOutcome {
+    /// Parse the page the website responds with after a submission.
+    pub fn parse(html: &str) -> Result<Self, AocClientError> {
+        let text = html::articles(html)
+            .first()
+            .map_or_else(|| html::to_text(html), |article| html::to_text(article));
+
+        if text.contains("That's the right answer") {
+            Ok(SubmitOutcome::Correct)
+        } else if text.contains("That's not the right answer") {
+            if text.contains("too high") {
+                Ok(SubmitOutcome::TooHigh)
+            } else if text.contains("too low") {
+                Ok(SubmitOutcome::TooLow)
+            } else {
+                Ok(SubmitOutcome::Incorrect)
+            }
+        } else if text.contains("You gave an answer too recently") {
+            let wait = parse_wait(&text).ok_or(AocClientError::UnexpectedResponse(text))?;
+            Ok(SubmitOutcome::RateLimited(wait))
+        } else if text.contains("You don't seem to be solving the right level") {
+            Ok(SubmitOutcome::AlreadySolved)
+        } else {
+            Err(AocClientError::UnexpectedResponse(text))
+        }
+    }
+}
+
+/// Parse the remaining wait of a rate-limited submission, e.g. "You have 1m 5s left to wait."
+fn parse_wait(text: &str) -> Option<Duration> {
+    let (before, _) = text.split_once(" left to wait")?;
+    let (_, wait) = before.rsplit_once("have ")?;
+
+    let mut secs = 0;
+    for token in wait.split_whitespace() {
+        let unit_pos = token.find(|c: char| !c.is_ascii_digit())?;
+        let value: u64 = token[..unit_pos].parse().ok()?;
+        secs += match &token[unit_pos..] {
+            "h" => value * 3600,
+            "m" => value * 60,
+            "s" => value,
+            _ => return None,
+        };
+    }
+
+    Some(Duration::from_secs(secs))
+}
+
+/// The `AOC_USER_AGENT` environment variable, or the name and version of this crate.
+fn user_agent() -> String {
+    match env::var("AOC_USER_AGENT") {
+        Ok(agent) if !agent.trim().is_empty() => agent.trim().into(),
+        _ => DEFAULT_USER_AGENT.into(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(session: &str) -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.into(),
+            session: session.trim().into(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(&user_agent())
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Create a client from the environment.
+    /// The session cookie is read from the `AOC_SESSION` environment variable or the session file (see [`get_session_path`]),
+    /// the base url can be overridden with `AOC_BASE_URL`.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let session = match env::var("AOC_SESSION") {
+            Ok(session) if !session.trim().is_empty() => session,
+            _ => get_session_path()
+                .and_then(|path| fs::read_to_string(path).ok())
+                .filter(|session| !session.trim().is_empty())
+                .ok_or(AocClientError::SessionNotFound)?,
+        };
+
+        let client = Self::new(&session);
+
+        Ok(match env::var("AOC_BASE_URL") {
+            Ok(base_url) if !base_url.is_empty() => client.with_base_url(&base_url),
+            _ => client,
+        })
+    }
+
+    /// Send requests to a different server, e.g. a local stand-in for tests.
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').into();
+        self
+    }
+
+    /// Fetch the puzzle input of a day.
+    pub fn get_input(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("/{year}/day/{}/input", day.into_inner()))
+    }
+
+    /// Fetch the puzzle page of a day as HTML. Once part one is solved, it includes part two.
+    pub fn get_puzzle(&self, year: Year, day: Day) -> Result<String, AocClientError> {
+        self.get(&format!("/{year}/day/{}", day.into_inner()))
+    }
+
+    /// Submit an answer for a part (`1` or `2`).
+    pub fn submit(
+        &self,
+        year: Year,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmitOutcome, AocClientError> {
+        let url = format!("{}/{year}/day/{}/answer", self.base_url, day.into_inner());
+
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer.trim())]);
+
+        SubmitOutcome::parse(&read_response(response)?)
+    }
+
+    fn get(&self, path: &str) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&format!("{}{path}", self.base_url))
+            .set("Cookie", &self.cookie())
+            .call();
+
+        read_response(response)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, AocClientError> {
+    match response {
+        Ok(response) => response.into_string().map_err(AocClientError::IO),
+        // the website responds with 400 if the session is missing and with 500 if it is invalid.
+        Err(ureq::Error::Status(400 | 401 | 500, _)) => Err(AocClientError::Unauthorized),
+        Err(ureq::Error::Status(404, _)) => Err(AocClientError::NotFound),
+        Err(ureq::Error::Status(status, _)) => Err(AocClientError::BadStatus(status)),
+        Err(ureq::Error::Transport(e)) => Err(AocClientError::Transport(e.to_string())),
+    }
+}
+
+/// The session file is looked up at `AOC_SESSION_FILE`, `~/.adventofcode.session` or `<config_dir>/adventofcode.session`.
+pub fn get_session_path() -> Option<PathBuf> {
+    if let Ok(path) = env::var("AOC_SESSION_FILE") {
+        return Some(PathBuf::from(path));
+    }
+
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from);
+
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".config")));
+
+    [
+        home.map(|home| home.join(".adventofcode.session")),
+        config_dir.map(|dir| dir.join("adventofcode.session")),
+    ]
+    .into_iter()
+    .flatten()
+    .find(|path| path.exists())
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub fn get_input_path(year: Year, day: Day) -> String {
+    get_data_path(year, &format!("inputs/{day}.txt"))
+}
+
+pub fn get_puzzle_path(year: Year, day: Day) -> String {
+    get_data_path(year, &format!("puzzles/{day}.md"))
+}
+
+/// Convert a puzzle page to markdown. Only the puzzle descriptions are kept.
+pub fn puzzle_to_markdown(html: &str) -> String {
+    html::articles(html)
+        .into_iter()
+        .map(html::to_markdown)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Download the input and puzzle description of a day to the data directory.
+pub fn download(year: Year, day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+
+    let input = client.get_input(year, day)?;
+    let input_path = get_input_path(year, day);
+
+    fs::create_dir_all(get_data_path(year, "inputs"))?;
+    fs::write(&input_path, input)?;
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+
+    download_puzzle(&client, year, day)?;
+    Ok(())
+}
+
+/// Download the puzzle description of a day again, e.g. after part two was unlocked.
+/// Returns the description as markdown.
+pub fn refresh_puzzle(year: Year, day: Day) -> Result<String, AocClientError> {
+    let client = AocClient::from_env()?;
+    download_puzzle(&client, year, day)
+}
+
+fn download_puzzle(client: &AocClient, year: Year, day: Day) -> Result<String, AocClientError> {
+    let puzzle = puzzle_to_markdown(&client.get_puzzle(year, day)?);
+    let puzzle_path = get_puzzle_path(year, day);
+
+    fs::create_dir_all(get_data_path(year, "puzzles"))?;
+    fs::write(&puzzle_path, &puzzle)?;
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+
+    Ok(puzzle)
+}
+
+/// Submit an answer and print the outcome. Correct answers are recorded in the answers file.
+pub fn submit(
+    year: Year,
+    day: Day,
+    part: u8,
+    answer: &str,
+) -> Result<SubmitOutcome, AocClientError> {
+    let client = AocClient::from_env()?;
+    let outcome = client.submit(year, day, part, answer)?;
+
+    println!("{outcome}");
+
+    if outcome == SubmitOutcome::Correct {
+        match answers::record(year, day, part, answer) {
+            Ok(()) => println!(
+                "🎄 Recorded answer in \"{}\".",
+                answers::get_answers_path(year)
+            ),
+            Err(e) => eprintln!("Failed to record answer: {e}"),
+        }
+    }
+
+    Ok(outcome)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{puzzle_to_markdown, SubmitOutcome};
+
+    fn response(text: &str) -> String {
+        format!("<html><body><main><article><p>{text}</p></article></main></body></html>")
+    }
+
+    #[test]
+    fn parses_submit_outcomes() {
+        let cases = [
+            (
+                "That's the right answer! You are <em>one gold star</em> closer.",
+                SubmitOutcome::Correct,
+            ),
+            (
+                "That's not the right answer. If you're stuck, make sure you're using the full input data.",
+                SubmitOutcome::Incorrect,
+            ),
+            (
+                "That's not the right answer; your answer is too high. Please wait one minute before trying again.",
+                SubmitOutcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                SubmitOutcome::TooLow,
+            ),
+            (
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait.",
+                SubmitOutcome::RateLimited(Duration::from_secs(34)),
+            ),
+            (
+                "You gave an answer too recently. You have 4m 2s left to wait.",
+                SubmitOutcome::RateLimited(Duration::from_secs(242)),
+            ),
+            (
+                "You don't seem to be solving the right level.  Did you already complete it?",
+                SubmitOutcome::AlreadySolved,
+            ),
+        ];
+
+        for (text, expected) in cases {
+            assert_eq!(SubmitOutcome::parse(&response(text)).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_responses() {
+        assert!(SubmitOutcome::parse(&response("Something else.")).is_err());
+    }
+
+    #[test]
+    fn converts_puzzle_pages() {
+        let html = r#"<main><article class="day-desc"><h2>--- Day 1: Test ---</h2><p>One.</p></article>
+<p>Your puzzle answer was <code>1</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Two.</p></article></main>"#;
+
+        assert_eq!(
+            puzzle_to_markdown(html),
+            "## --- Day 1: Test ---\n\nOne.\n\n## --- Part Two ---\n\nTwo.\n"
+        );
+    }
+}
@@ -1,5 +1,27 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{
+    all_days,
+    config::Timeouts,
+    run_multi::{run_multi, RunMultiOptions},
+    runner::Solution,
+    Year,
+};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(
+    year: Year,
+    is_release: bool,
+    jobs: usize,
+    timeouts: Timeouts,
+    registry: Option<&[Solution]>,
+) {
+    run_multi(
+        year,
+        &all_days(year).collect(),
+        &RunMultiOptions {
+            is_release,
+            registry,
+            jobs,
+            timeouts,
+            ..RunMultiOptions::default()
+        },
+    );
 }
@@ -0,0 +1,150 @@
+use std::{
+    fs,
+    io::{self, BufRead, IsTerminal, Write},
+    process,
+};
+
+use crate::template::aoc_client::get_puzzle_path;
+use crate::template::puzzle::{self, CodeBlock};
+use crate::template::{get_data_path, Day, Year};
+
+/// How many lines of a block are shown in the picker.
+const PREVIEW_LINES: usize = 5;
+
+/// Extract the example inputs from the downloaded puzzle description.
+/// The example of part one is written to `<day>.txt`. If part two has a different example, the examples of
+/// both parts are also written to `<day>-1.txt` and `<day>-2.txt`.
+pub fn handle(year: Year, day: Day, overwrite: bool) {
+    let Some(markdown) = puzzle::read(year, day) else {
+        eprintln!(
+            "Puzzle description not found at \"{}\". Download it first, e.g. with `cargo download {day}`.",
+            get_puzzle_path(year, day)
+        );
+        process::exit(1);
+    };
+
+    let blocks = puzzle::code_blocks(&markdown);
+
+    let Some(part_one) = select(&blocks, 1) else {
+        eprintln!("Could not find an example in the puzzle description.");
+        process::exit(1);
+    };
+
+    if let Err(e) = fs::create_dir_all(get_data_path(year, "examples")) {
+        eprintln!("Failed to create data directory: {e}");
+        process::exit(1);
+    }
+
+    write_example(
+        &get_data_path(year, &format!("examples/{day}.txt")),
+        &part_one.content,
+        overwrite,
+    );
+
+    write_part_two(year, day, &blocks, &part_one.content, overwrite);
+}
+
+/// Extract the example of part two once it is unlocked. The example of part one is left as is.
+pub fn handle_part_two(year: Year, day: Day) {
+    let Some(markdown) = puzzle::read(year, day) else {
+        return;
+    };
+
+    let part_one =
+        fs::read_to_string(get_data_path(year, &format!("examples/{day}.txt"))).unwrap_or_default();
+
+    write_part_two(year, day, &puzzle::code_blocks(&markdown), &part_one, false);
+}
+
+/// Write the examples of both parts to `<day>-1.txt` and `<day>-2.txt` if part two has a different example.
+fn write_part_two(year: Year, day: Day, blocks: &[CodeBlock], part_one: &str, overwrite: bool) {
+    if let Some(part_two) = select(blocks, 2).filter(|b| b.content != part_one) {
+        write_example(
+            &get_data_path(year, &format!("examples/{day}-1.txt")),
+            part_one,
+            overwrite,
+        );
+        write_example(
+            &get_data_path(year, &format!("examples/{day}-2.txt")),
+            &part_two.content,
+            overwrite,
+        );
+        println!("🎄 Part two has its own example, read the examples with `read_file_part(\"examples\", YEAR, DAY, 1)` and `read_file_part(\"examples\", YEAR, DAY, 2)`.");
+    }
+}
+
+/// Select the example of a part. If several blocks are introduced as an example, the user picks one.
+/// Part one falls back to its first block if none is introduced as an example.
+fn select(blocks: &[CodeBlock], part: u8) -> Option<&CodeBlock> {
+    let in_part: Vec<&CodeBlock> = blocks.iter().filter(|b| b.part == part).collect();
+    let candidates: Vec<&CodeBlock> = in_part.iter().copied().filter(|b| b.is_example()).collect();
+
+    match candidates.len() {
+        0 if part == 1 => in_part.first().copied(),
+        0 => None,
+        1 => Some(candidates[0]),
+        _ => Some(pick(&candidates, part)),
+    }
+}
+
+fn pick<'a>(candidates: &[&'a CodeBlock], part: u8) -> &'a CodeBlock {
+    let stdin = io::stdin();
+
+    if !stdin.is_terminal() {
+        println!(
+            "Found {} possible examples for part {part}, using the first one.",
+            candidates.len()
+        );
+        return candidates[0];
+    }
+
+    println!(
+        "Found {} possible examples for part {part}:",
+        candidates.len()
+    );
+
+    for (i, block) in candidates.iter().enumerate() {
+        println!("\n[{}] {}", i + 1, block.context);
+        for line in block.content.lines().take(PREVIEW_LINES) {
+            println!("    {line}");
+        }
+        if block.content.lines().count() > PREVIEW_LINES {
+            println!("    ...");
+        }
+    }
+
+    loop {
+        print!("\nPick an example [1-{}, default 1]: ", candidates.len());
+        let _ = io::stdout().flush();
+
+        let mut line = String::new();
+        match stdin.lock().read_line(&mut line) {
+            // stdin was closed, use the default.
+            Ok(0) | Err(_) => return candidates[0],
+            Ok(_) if line.trim().is_empty() => return candidates[0],
+            Ok(_) => {}
+        }
+
+        match line.trim().parse::<usize>() {
+            Ok(n) if (1..=candidates.len()).contains(&n) => return candidates[n - 1],
+            _ => println!("Please enter a number between 1 and {}.", candidates.len()),
+        }
+    }
+}
+
+fn write_example(path: &str, content: &str, overwrite: bool) {
+    let has_content = fs::read_to_string(path).is_ok_and(|s| !s.trim().is_empty());
+
+    if has_content && !overwrite {
+        eprintln!("\"{path}\" already has content, pass `--overwrite` to replace it.");
+        return;
+    }
+
+    match fs::write(path, content) {
+        Ok(()) => println!("Wrote example to \"{path}\"."),
+        Err(e) => {
+            eprintln!("Failed to write example: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::{fs, process};
+
+pub use crate::template::export::Format;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{export, readme_benchmarks, Day, Year};
+
+/// Settings of `--export <format> <path>`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExportOptions {
+    pub format: Format,
+    pub path: String,
+    /// Embed the chart in the benchmark table of the readme (`--readme`).
+    pub readme: bool,
+}
+
+/// Export the stored timings of `year`, or of a single day, without running any benchmarks.
+pub fn handle(year: Year, day: Option<Day>, options: &ExportOptions) {
+    if options.readme && options.format != Format::Svg {
+        eprintln!("`--readme` can only embed a chart, use `--export svg <path>`.");
+        process::exit(1);
+    }
+
+    if options.readme && day.is_some() {
+        eprintln!("`--readme` embeds a chart of all days, it can not be combined with a day.");
+        process::exit(1);
+    }
+
+    let stored_timings = Timings::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read timings: {e}");
+        process::exit(1);
+    });
+
+    if stored_timings.data.is_empty() {
+        eprintln!("No stored timings to export, run `cargo time --store` first.");
+        process::exit(1);
+    }
+
+    let timings = match day {
+        Some(day) => {
+            let data: Vec<Timing> = stored_timings
+                .data
+                .iter()
+                .filter(|t| t.day == day)
+                .cloned()
+                .collect();
+            Timings { data }
+        }
+        None => stored_timings.clone(),
+    };
+
+    let output = export::render(year, &timings, options.format).unwrap_or_else(|e| {
+        eprintln!("Failed to export timings: {e}");
+        process::exit(1);
+    });
+
+    if let Err(e) = fs::write(&options.path, output) {
+        eprintln!("Failed to write \"{}\": {e}", options.path);
+        process::exit(1);
+    }
+
+    println!(
+        "Exported timings as {} to \"{}\".",
+        options.format, options.path
+    );
+
+    if options.readme {
+        match readme_benchmarks::embed_chart(year, stored_timings, &options.path) {
+            Ok(()) => println!("Embedded chart in the readme."),
+            Err(_) => {
+                eprintln!("Failed to embed chart in the readme.");
+                process::exit(1);
+            }
+        }
+    }
+}
@@ -1,15 +1,33 @@
+use std::io::{self, IsTerminal};
 use std::process;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_client, markdown, puzzle, Day, Year};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+/// Print the puzzle description of a day, or of one of its parts.
+/// The downloaded description is used if present, the puzzle is only downloaded if it is missing or part two is not in it yet.
+pub fn handle(year: Year, day: Day, part: Option<u8>) {
+    let description = match puzzle::read(year, day) {
+        Some(description) if part != Some(2) || puzzle::part_two(&description).is_some() => {
+            description
+        }
+        _ => aoc_client::refresh_puzzle(year, day).unwrap_or_else(|e| {
+            eprintln!("Failed to read puzzle: {e}");
+            process::exit(1);
+        }),
+    };
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let section = match part {
+        Some(1) => puzzle::part_one(&description),
+        Some(_) => puzzle::part_two(&description).unwrap_or_else(|| {
+            eprintln!("Part two is not unlocked yet, solve part one first.");
+            process::exit(1);
+        }),
+        None => &description,
     };
+
+    if io::stdout().is_terminal() {
+        print!("{}", markdown::to_ansi(section));
+    } else {
+        print!("{section}");
+    }
 }
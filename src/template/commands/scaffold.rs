@@ -1,23 +1,24 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::commands::download;
+use crate::template::config::Config;
+use crate::template::{get_bin_path, get_data_path, puzzle, Day, Year};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
-    }
-    file.truncate(true).write(true).open(path)
-}
+/// Directory of the named templates, e.g. `templates/grid.txt`.
+const TEMPLATES_DIR: &str = "templates";
+
+/// Name of the built-in template. It can be replaced by adding `templates/default.txt`.
+const DEFAULT_TEMPLATE: &str = "default";
+
+const DEFAULT_RETURN_TYPE: &str = "u32";
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new()
@@ -27,12 +28,131 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Create an empty data file. Existing files are kept unless `overwrite` is set.
+fn create_data_file(path: &str, kind: &str, overwrite: bool) {
+    if Path::new(path).exists() && !overwrite {
+        return;
+    }
+
+    match create_file(path) {
+        Ok(_) => {
+            println!("Created empty {kind} file \"{path}\"");
+        }
+        Err(e) => {
+            eprintln!("Failed to create {kind} file: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Scaffold a day from a template. `template` and `return_type` fall back to the `scaffold` section of the config.
+/// The puzzle is downloaded before the module is created, so its title can be filled in.
+pub fn handle(
+    year: Year,
+    day: Day,
+    template: Option<&str>,
+    return_type: Option<&str>,
+    download: bool,
+    overwrite: bool,
+) {
+    let config = Config::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read config: {e}");
+        process::exit(1);
+    });
+
+    let template_name = template
+        .or(config.scaffold.template.as_deref())
+        .unwrap_or(DEFAULT_TEMPLATE);
+
+    let template = load_template(template_name).unwrap_or_else(|e| {
+        eprintln!("Failed to load template: {e}");
+        process::exit(1);
+    });
+
+    let return_type = return_type
+        .or(config.scaffold.return_type.as_deref())
+        .unwrap_or(DEFAULT_RETURN_TYPE);
+
+    let input_path = get_data_path(year, &format!("inputs/{day}.txt"));
+    let example_path = get_data_path(year, &format!("examples/{day}.txt"));
+    let module_path = get_bin_path(year, day);
+
+    for dir in ["inputs", "examples"] {
+        if let Err(e) = fs::create_dir_all(get_data_path(year, dir)) {
+            eprintln!("Failed to create data directory: {e}");
+            process::exit(1);
+        }
+    }
+
+    create_data_file(&input_path, "input", overwrite);
+    create_data_file(&example_path, "example", overwrite);
+
+    if download {
+        download::handle(year, day);
+    }
+
+    if Path::new(&module_path).exists() && !overwrite {
+        println!("Module file \"{module_path}\" exists, keeping it.");
+    } else {
+        let markdown = puzzle::read(year, day);
+        let title = markdown.as_deref().and_then(puzzle::title);
+        let contents = render(&template, year, day, title, return_type);
+        create_module(&module_path, &contents);
+    }
+
+    println!("---");
+    if Year::from_env() == Some(year) {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    } else {
+        println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+    }
+}
+
+/// Read a named template from the templates directory.
+fn load_template(name: &str) -> Result<String, String> {
+    let path = format!("{TEMPLATES_DIR}/{name}.txt");
+
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(_) if name == DEFAULT_TEMPLATE => Ok(MODULE_TEMPLATE.to_string()),
+        Err(_) => Err(format!(
+            "template `{name}` not found at \"{path}\". Available templates: {}.",
+            available_templates().join(", ")
+        )),
+    }
+}
+
+fn available_templates() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(TEMPLATES_DIR)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            name.strip_suffix(".txt").map(String::from)
+        })
+        .collect();
+
+    names.push(DEFAULT_TEMPLATE.to_string());
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Fill in the placeholders of a template. Without a downloaded puzzle, the title is e.g. `Day 1`.
+fn render(template: &str, year: Year, day: Day, title: Option<&str>, return_type: &str) -> String {
+    let title = title.map_or_else(|| format!("Day {}", day.into_inner()), String::from);
+
+    template
+        .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        .replace("%DAY%", &day.to_string())
+        .replace("%YEAR%", &year.to_string())
+        .replace("%RETURN_TYPE%", return_type)
+        .replace("%TITLE%", &title)
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
+fn create_module(module_path: &str, contents: &str) {
+    let mut file = match create_file(module_path) {
         Ok(file) => file,
         Err(e) => {
             eprintln!("Failed to create module file: {e}");
@@ -40,40 +160,210 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(contents.as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{module_path}\"");
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
             process::exit(1);
         }
     }
+}
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+/// Fill in the expected results of the module's example tests with the answers in the downloaded puzzle description.
+/// Only assertions that still expect `None` are changed, so expectations set by hand are kept.
+pub fn update_tests(year: Year, day: Day) {
+    let Some(markdown) = puzzle::read(year, day) else {
+        return;
+    };
+
+    let module_path = get_bin_path(year, day);
+    let Ok(mut source) = fs::read_to_string(&module_path) else {
+        return;
+    };
+
+    let mut updated = false;
+
+    for (part, answer) in puzzle::example_answers(&markdown).iter().enumerate() {
+        let Some(answer) = answer else {
+            continue;
+        };
+
+        let test = if part == 0 {
+            "test_part_one"
+        } else {
+            "test_part_two"
+        };
+
+        match set_expected(&source, test, answer) {
+            Ok(Some(s)) => {
+                println!("Expecting `{answer}` in `{test}`.");
+                source = s;
+                updated = true;
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("{e}"),
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
+    }
+
+    if updated {
+        if let Err(e) = fs::write(&module_path, source) {
+            eprintln!("Failed to update module tests: {e}");
             process::exit(1);
         }
     }
+}
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+/// Replace the `None` expectation of a test function with the answer, typed like the return type of the tested part.
+/// Returns `None` if the test does not exist or does not expect `None`, and an error if the answer does not fit the return type.
+fn set_expected(source: &str, test: &str, answer: &str) -> Result<Option<String>, String> {
+    const STUB: &str = "assert_eq!(result, None);";
+
+    let Some(start) = source.find(&format!("fn {test}()")) else {
+        return Ok(None);
+    };
+    // the body of the test ends where the next function starts.
+    let body = &source[start + 3..];
+    let end = body.find("fn ").map_or(source.len(), |i| start + 3 + i);
+    let Some(stub) = source[start..end].find(STUB).map(|i| start + i) else {
+        return Ok(None);
+    };
+
+    let return_type = return_type(source, test.trim_start_matches("test_"));
+    let returns_string = return_type.is_some_and(|t| t == "String" || t.ends_with("::String"));
+
+    let expected = match return_type.and_then(|t| fits_integer(t, answer)) {
+        _ if returns_string => format!("Some({answer:?}.to_string())"),
+        Some(true) => format!("Some({answer})"),
+        Some(false) => {
+            return Err(format!(
+                "The answer `{answer}` does not fit the return type `{}`, `{test}` still expects `None`.",
+                return_type.unwrap_or_default()
+            ))
         }
+        None if answer.parse::<i128>().is_ok() => format!("Some({answer})"),
+        None => format!("Some({answer:?}.to_string())"),
+    };
+
+    Ok(Some(format!(
+        "{}assert_eq!(result, {expected});{}",
+        &source[..stub],
+        &source[stub + STUB.len()..]
+    )))
+}
+
+/// Whether an answer can be written as a literal of an integer type. Returns `None` for other types.
+fn fits_integer(type_name: &str, answer: &str) -> Option<bool> {
+    let fits = match type_name {
+        "u8" => answer.parse::<u8>().is_ok(),
+        "u16" => answer.parse::<u16>().is_ok(),
+        "u32" => answer.parse::<u32>().is_ok(),
+        "u64" => answer.parse::<u64>().is_ok(),
+        "u128" => answer.parse::<u128>().is_ok(),
+        "usize" => answer.parse::<usize>().is_ok(),
+        "i8" => answer.parse::<i8>().is_ok(),
+        "i16" => answer.parse::<i16>().is_ok(),
+        "i32" => answer.parse::<i32>().is_ok(),
+        "i64" => answer.parse::<i64>().is_ok(),
+        "i128" => answer.parse::<i128>().is_ok(),
+        "isize" => answer.parse::<isize>().is_ok(),
+        _ => return None,
+    };
+    Some(fits)
+}
+
+/// The `T` of a part declared as `fn <part>(...) -> Option<T>`.
+fn return_type<'a>(source: &'a str, part: &str) -> Option<&'a str> {
+    let start = source.find(&format!("fn {part}("))?;
+    let signature = &source[start..start + source[start..].find('{')?];
+    let (_, ret) = signature.split_once("-> Option<")?;
+    Some(ret[..ret.rfind('>')?].trim())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, set_expected, MODULE_TEMPLATE};
+    use crate::day;
+    use crate::template::Year;
+
+    #[test]
+    fn fills_in_placeholders() {
+        let template = "// %TITLE% (%YEAR%-%DAY%)\nsolution!(%DAY_NUMBER%);\nfn part_one() -> Option<%RETURN_TYPE%>";
+
+        assert_eq!(
+            render(
+                template,
+                Year::new(2024).unwrap(),
+                day!(5),
+                Some("Print Queue"),
+                "u64"
+            ),
+            "// Print Queue (2024-05)\nsolution!(5);\nfn part_one() -> Option<u64>"
+        );
+        assert_eq!(
+            render(template, Year::new(2024).unwrap(), day!(5), None, "u32"),
+            "// Day 5 (2024-05)\nsolution!(5);\nfn part_one() -> Option<u32>"
+        );
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    #[test]
+    fn sets_expected_results() {
+        let source = set_expected(MODULE_TEMPLATE, "test_part_one", "11")
+            .unwrap()
+            .unwrap();
+        assert!(source.contains("assert_eq!(result, Some(11));"));
+        // part two is left untouched.
+        assert_eq!(source.matches("assert_eq!(result, None);").count(), 1);
+
+        let source = set_expected(&source, "test_part_two", "abc")
+            .unwrap()
+            .unwrap();
+        assert!(source.contains("assert_eq!(result, Some(\"abc\".to_string()));"));
+
+        // expectations that were already set are kept.
+        assert_eq!(set_expected(&source, "test_part_one", "12"), Ok(None));
+        assert_eq!(set_expected(&source, "test_part_three", "12"), Ok(None));
+    }
+
+    #[test]
+    fn sets_expected_strings_by_return_type() {
+        let source = MODULE_TEMPLATE.replace("%RETURN_TYPE%", "String");
+        let source = set_expected(&source, "test_part_one", "3")
+            .unwrap()
+            .unwrap();
+        assert!(source.contains("assert_eq!(result, Some(\"3\".to_string()));"));
+
+        let source = MODULE_TEMPLATE
+            .replacen("%RETURN_TYPE%", "u64", 1)
+            .replacen("%RETURN_TYPE%", "std::string::String", 1);
+        let source = set_expected(&source, "test_part_one", "3")
+            .unwrap()
+            .unwrap();
+        let source = set_expected(&source, "test_part_two", "3")
+            .unwrap()
+            .unwrap();
+        assert!(source.contains("assert_eq!(result, Some(3));"));
+        assert!(source.contains("assert_eq!(result, Some(\"3\".to_string()));"));
+    }
+
+    #[test]
+    fn keeps_answers_that_do_not_fit_the_return_type() {
+        let source = MODULE_TEMPLATE.replace("%RETURN_TYPE%", "u32");
+        assert!(set_expected(&source, "test_part_one", "-5").is_err());
+        assert!(set_expected(&source, "test_part_one", "5000000000").is_err());
+        assert!(set_expected(&source, "test_part_one", "abc").is_err());
+
+        let source = MODULE_TEMPLATE.replace("%RETURN_TYPE%", "i64");
+        let source = set_expected(&source, "test_part_one", "-5")
+            .unwrap()
+            .unwrap();
+        let source = set_expected(&source, "test_part_two", "5000000000")
+            .unwrap()
+            .unwrap();
+        assert!(source.contains("assert_eq!(result, Some(-5));"));
+        assert!(source.contains("assert_eq!(result, Some(5000000000));"));
+    }
 }
@@ -1,9 +1,31 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
+use std::time::Instant;
 
-use crate::template::Day;
+use crate::template::{
+    config::{BenchOverrides, Config, Timeouts},
+    get_bin_name,
+    run_multi::child_commands::wait_until,
+    runner::TIMEOUT_EXIT_CODE,
+    Day, Year,
+};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    day: Day,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    no_wait: bool,
+    examples: bool,
+    time: Option<&BenchOverrides>,
+    timeouts: &Timeouts,
+) {
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--bin".to_string(),
+        get_bin_name(year, day),
+    ];
 
     if dhat {
         cmd_args.extend([
@@ -16,6 +38,32 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    if cfg!(feature = "alloc-metrics") {
+        // count the allocations of the solution bin as well.
+        cmd_args.extend(["--features".to_string(), "alloc-metrics".to_string()]);
+    }
+
+    let config = Config::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read config: {e}");
+        process::exit(1);
+    });
+    let day_timeout = Timeouts::resolve(&config, year, day, timeouts).day;
+
+    if day_timeout.is_some() {
+        // build upfront, so compilation does not count towards the day timeout.
+        let mut build_args = cmd_args.clone();
+        build_args[0] = "build".to_string();
+        let status = Command::new("cargo")
+            .args(&build_args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::inherit())
+            .status()
+            .unwrap();
+        if !status.success() {
+            process::exit(status.code().unwrap_or(1));
+        }
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,6 +71,21 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if no_wait {
+        cmd_args.push("--no-wait".to_string());
+    }
+
+    if examples {
+        cmd_args.push("--examples".to_string());
+    }
+
+    if let Some(bench) = time {
+        cmd_args.push("--time".to_string());
+        cmd_args.extend(bench.to_args());
+    }
+
+    cmd_args.extend(timeouts.to_args());
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -30,5 +93,17 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    // the day timeout is enforced here, part timeouts are enforced by the solution bin itself.
+    let deadline = day_timeout.map(|timeout| Instant::now() + timeout);
+    if wait_until(&mut cmd, deadline).unwrap() {
+        let timeout = day_timeout.unwrap_or_default();
+        println!("TIMEOUT: day did not finish within {timeout:.1?}, stopped.");
+        process::exit(TIMEOUT_EXIT_CODE);
+    }
+
+    // forward the exit status, e.g. if a part panicked or timed out.
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
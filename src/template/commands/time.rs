@@ -1,19 +1,49 @@
 use std::collections::HashSet;
+use std::process;
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::config::{BenchOverrides, Timeouts};
+use crate::template::run_multi::{run_multi, RunMultiOptions};
+use crate::template::runner::Solution;
+use crate::template::timings::{format_nanos, Change, TimingMeta, Timings};
+use crate::template::{all_days, readme_benchmarks, Day, Year};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Default change in percent above which a slower part is flagged as a regression.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// Settings of `--compare`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompareOptions {
+    /// Label or commit of the stored timings to compare with (`--baseline <name>`). The previous timings if not set.
+    pub baseline: Option<String>,
+    /// Change in percent above which a part is flagged as a regression (`--threshold <percent>`).
+    pub threshold: f64,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    label: Option<String>,
+    compare: Option<&CompareOptions>,
+    bench: &BenchOverrides,
+    timeouts: Timeouts,
+    registry: Option<&[Solution]>,
+) {
+    let stored_timings = Timings::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read timings: {e}");
+        process::exit(1);
+    });
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
-                all_days().collect()
+            // comparisons need fresh timings of every day.
+            if run_all || compare.is_some() {
+                all_days(year).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                all_days(year)
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
@@ -21,14 +51,33 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let timings = run_multi(
+        year,
+        &days_to_run,
+        &RunMultiOptions {
+            is_release: true,
+            is_timed: true,
+            bench: bench.clone(),
+            registry,
+            jobs: 1,
+            timeouts,
+        },
+    )
+    .timings
+    .unwrap();
+
+    if let Some(compare) = compare {
+        let baseline = stored_timings.baseline(compare.baseline.as_deref());
+        print_comparison(&timings.compare(&baseline), compare);
+    }
 
     if store {
+        let timings = timings.with_meta(&TimingMeta::current(label));
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -38,3 +87,49 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+fn print_comparison(changes: &[Change], options: &CompareOptions) {
+    println!();
+
+    match &options.baseline {
+        Some(name) => println!("Compared with baseline `{name}`:"),
+        None => println!("Compared with the previous timings:"),
+    }
+
+    if changes.is_empty() {
+        println!("No stored timings to compare with.");
+        return;
+    }
+
+    for change in changes {
+        let part = match change.part {
+            0 => "Parse".to_string(),
+            part => format!("Part {part}"),
+        };
+
+        let flag = if change.is_regression(options.threshold) {
+            " ⚠ regression"
+        } else {
+            ""
+        };
+
+        println!(
+            "Day {} {part}: {} -> {} ({:+.1}%){flag}",
+            change.day,
+            format_nanos(change.before_nanos),
+            format_nanos(change.after_nanos),
+            change.percent(),
+        );
+    }
+
+    let regressions = changes
+        .iter()
+        .filter(|c| c.is_regression(options.threshold))
+        .count();
+
+    match regressions {
+        0 => println!("No regressions above {}%.", options.threshold),
+        1 => println!("1 regression above {}%.", options.threshold),
+        n => println!("{n} regressions above {}%.", options.threshold),
+    }
+}
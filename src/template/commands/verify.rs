@@ -0,0 +1,158 @@
+use std::{collections::HashSet, process};
+
+use crate::template::answers::{get_answers_path, Answers};
+use crate::template::config::Timeouts;
+use crate::template::report::{PartReport, Status};
+use crate::template::run_multi::{run_multi, RunMultiOptions};
+use crate::template::runner::Solution;
+use crate::template::{Day, Year, ANSI_BOLD, ANSI_RESET};
+
+enum Outcome {
+    Pass,
+    Fail,
+    /// There is no known answer to compare against.
+    Unknown,
+}
+
+struct Row {
+    day: Day,
+    part: u8,
+    expected: String,
+    actual: String,
+    outcome: Outcome,
+}
+
+pub fn handle(
+    year: Year,
+    day: Option<Day>,
+    is_release: bool,
+    jobs: usize,
+    timeouts: Timeouts,
+    registry: Option<&[Solution]>,
+) {
+    let answers = Answers::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read answers: {e}");
+        process::exit(1);
+    });
+
+    // without a day, verify every day that has a known answer.
+    let days_to_run: HashSet<Day> = day.map_or_else(
+        || answers.days().into_iter().collect(),
+        |day| HashSet::from([day]),
+    );
+
+    if days_to_run.is_empty() {
+        println!(
+            "No known answers. Submit a solution or add answers to \"{}\".",
+            get_answers_path(year)
+        );
+        return;
+    }
+
+    let result = run_multi(
+        year,
+        &days_to_run,
+        &RunMultiOptions {
+            is_release,
+            jobs,
+            timeouts,
+            registry,
+            ..RunMultiOptions::default()
+        },
+    );
+
+    let mut days: Vec<Day> = days_to_run.into_iter().collect();
+    days.sort_unstable();
+
+    let rows: Vec<Row> = days
+        .into_iter()
+        .flat_map(|day| [(day, 1), (day, 2)])
+        .filter_map(|(day, part)| compare(&answers, &result.reports, day, part))
+        .collect();
+
+    print_table(&rows);
+
+    let failed = rows
+        .iter()
+        .filter(|r| matches!(r.outcome, Outcome::Fail))
+        .count();
+    let passed = rows
+        .iter()
+        .filter(|r| matches!(r.outcome, Outcome::Pass))
+        .count();
+
+    println!("\n{passed} passed, {failed} failed.");
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+fn compare(answers: &Answers, reports: &[PartReport], day: Day, part: u8) -> Option<Row> {
+    let expected = answers.get(day, part);
+    let report = reports.iter().find(|r| r.day == day && r.part == part);
+
+    if expected.is_none() && report.is_none() {
+        return None;
+    }
+
+    let actual = report.and_then(|r| match r.status {
+        Status::Solved => r.answer.clone(),
+        Status::Unsolved | Status::Running => None,
+        Status::Timeout => Some("TIMEOUT".into()),
+        Status::Panicked => Some("PANIC".into()),
+    });
+
+    let outcome = match expected {
+        None => Outcome::Unknown,
+        Some(expected) => {
+            let is_match = report.is_some_and(|r| r.status == Status::Solved)
+                && actual.as_deref().map(str::trim) == Some(expected);
+
+            if is_match {
+                Outcome::Pass
+            } else {
+                Outcome::Fail
+            }
+        }
+    };
+
+    Some(Row {
+        day,
+        part,
+        expected: expected.map_or("-".into(), escape),
+        actual: actual.as_deref().map_or("-".into(), escape),
+        outcome,
+    })
+}
+
+/// Multi-line answers are shown on a single line.
+fn escape(answer: &str) -> String {
+    answer.trim().replace('\n', "\\n")
+}
+
+fn print_table(rows: &[Row]) {
+    let expected_width = rows.iter().map(|r| r.expected.chars().count()).max();
+    let actual_width = rows.iter().map(|r| r.actual.chars().count()).max();
+
+    let expected_width = expected_width.unwrap_or(0).max("Expected".len());
+    let actual_width = actual_width.unwrap_or(0).max("Actual".len());
+
+    println!(
+        "\n{ANSI_BOLD}Day  Part  {:expected_width$}  {:actual_width$}  Result{ANSI_RESET}",
+        "Expected", "Actual"
+    );
+
+    for row in rows {
+        let outcome = match row.outcome {
+            Outcome::Pass => "✔ pass",
+            Outcome::Fail => "✖ FAIL",
+            Outcome::Unknown => "? unknown",
+        };
+
+        println!(
+            "{}   {}     {:expected_width$}  {:actual_width$}  {outcome}",
+            row.day, row.part, row.expected, row.actual
+        );
+    }
+}
@@ -0,0 +1,687 @@
+/// Optional, user-editable configuration stored in `data/config.json`.
+///
+/// Settings can be set globally and overridden for individual days of a year:
+/// ```json
+/// {
+///     "bench": { "warm_up_ms": 100, "measurement_ms": 1000, "min_samples": 10, "max_samples": 10000 },
+///     "timeout": { "part_ms": 10000, "day_ms": 30000 },
+///     "years": { "2024": { "days": { "06": { "bench": { "measurement_ms": 5000, "max_samples": 20 } } } } },
+///     "scaffold": { "template": "grid", "return_type": "u64" },
+///     "readme": { "columns": ["title", "stars", "share"], "slowest": 3, "per_year": true }
+/// }
+/// ```
+use std::{collections::HashMap, fs, str::FromStr, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year};
+
+static CONFIG_FILE_PATH: &str = "./data/config.json";
+
+/// Effective settings used when benching a solution part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchSettings {
+    /// Time spent running the solution without recording samples.
+    pub warm_up: Duration,
+    /// Targeted time spent recording samples.
+    pub measurement_time: Duration,
+    pub min_samples: u64,
+    pub max_samples: u64,
+}
+
+impl Default for BenchSettings {
+    fn default() -> Self {
+        Self {
+            warm_up: Duration::ZERO,
+            measurement_time: Duration::from_secs(1),
+            min_samples: 10,
+            max_samples: 10_000,
+        }
+    }
+}
+
+impl BenchSettings {
+    /// Resolve the settings for a day: defaults, overridden by the config file, overridden by command-line arguments.
+    pub fn resolve(config: &Config, year: Year, day: Day, args: &BenchOverrides) -> Self {
+        let mut settings = Self::default().apply(&config.bench);
+
+        if let Some(day_config) = config.days.get(&(year, day)) {
+            settings = settings.apply(&day_config.bench);
+        }
+
+        settings.apply(args)
+    }
+
+    fn apply(self, overrides: &BenchOverrides) -> Self {
+        let min_samples = overrides.min_samples.unwrap_or(self.min_samples).max(1);
+        Self {
+            warm_up: overrides.warm_up.unwrap_or(self.warm_up),
+            measurement_time: overrides.measurement_time.unwrap_or(self.measurement_time),
+            min_samples,
+            max_samples: overrides
+                .max_samples
+                .unwrap_or(self.max_samples)
+                .max(min_samples),
+        }
+    }
+}
+
+/// Partial [`BenchSettings`], as read from the config file or the command-line.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BenchOverrides {
+    pub warm_up: Option<Duration>,
+    pub measurement_time: Option<Duration>,
+    pub min_samples: Option<u64>,
+    pub max_samples: Option<u64>,
+}
+
+impl BenchOverrides {
+    /// Parse the `--warm-up <ms>`, `--measurement-time <ms>`, `--min-samples <n>` and `--max-samples <n>` options.
+    pub fn parse(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        Ok(Self {
+            warm_up: args
+                .opt_value_from_str("--warm-up")?
+                .map(Duration::from_millis),
+            measurement_time: args
+                .opt_value_from_str("--measurement-time")?
+                .map(Duration::from_millis),
+            min_samples: args.opt_value_from_str("--min-samples")?,
+            max_samples: args.opt_value_from_str("--max-samples")?,
+        })
+    }
+
+    /// Serialize back to command-line arguments, so they can be mirrored to child invocations.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if let Some(x) = self.warm_up {
+            args.extend(["--warm-up".into(), x.as_millis().to_string()]);
+        }
+        if let Some(x) = self.measurement_time {
+            args.extend(["--measurement-time".into(), x.as_millis().to_string()]);
+        }
+        if let Some(x) = self.min_samples {
+            args.extend(["--min-samples".into(), x.to_string()]);
+        }
+        if let Some(x) = self.max_samples {
+            args.extend(["--max-samples".into(), x.to_string()]);
+        }
+
+        args
+    }
+}
+
+/// Timeouts after which a running solution is stopped. Unset timeouts never expire.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Timeouts {
+    /// Time a single part (or the parse step) may take.
+    pub part: Option<Duration>,
+    /// Time all parts of a day may take together.
+    pub day: Option<Duration>,
+}
+
+impl Timeouts {
+    /// Resolve the timeouts for a day: the config file, overridden by command-line arguments.
+    pub fn resolve(config: &Config, year: Year, day: Day, args: &Timeouts) -> Self {
+        let mut timeouts = config.timeout;
+
+        if let Some(day_config) = config.days.get(&(year, day)) {
+            timeouts = timeouts.apply(&day_config.timeout);
+        }
+
+        timeouts.apply(args)
+    }
+
+    fn apply(self, overrides: &Timeouts) -> Self {
+        Self {
+            part: overrides.part.or(self.part),
+            day: overrides.day.or(self.day),
+        }
+    }
+
+    /// Parse the `--timeout <ms>` and `--day-timeout <ms>` options.
+    pub fn parse(args: &mut pico_args::Arguments) -> Result<Self, pico_args::Error> {
+        Ok(Self {
+            part: args
+                .opt_value_from_str("--timeout")?
+                .map(Duration::from_millis),
+            day: args
+                .opt_value_from_str("--day-timeout")?
+                .map(Duration::from_millis),
+        })
+    }
+
+    /// Serialize the part timeout back to command-line arguments, so it can be mirrored to solution bins.
+    /// The day timeout is enforced by the process that invokes the bin.
+    pub fn to_args(&self) -> Vec<String> {
+        match self.part {
+            Some(x) => vec!["--timeout".into(), x.as_millis().to_string()],
+            None => vec![],
+        }
+    }
+}
+
+/// Settings that apply to a single day of a year.
+#[derive(Clone, Debug, Default)]
+pub struct DayConfig {
+    pub bench: BenchOverrides,
+    pub timeout: Timeouts,
+}
+
+/// Defaults of the `scaffold` command.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ScaffoldConfig {
+    /// Name of the template in `templates/` that new days are created from.
+    pub template: Option<String>,
+    /// Return type of the solution parts, `u32` if not set.
+    pub return_type: Option<String>,
+}
+
+/// Optional column of the benchmark table in the readme.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Column {
+    /// Title of the puzzle, from `data/<year>/puzzles/<day>.md`.
+    Title,
+    /// Accepted answers, from `data/<year>/answers.json`.
+    Stars,
+    /// Time of the parse step. Shown by default if any solution has one.
+    Parse,
+    /// Peak heap usage, recorded with the `alloc-metrics` feature.
+    Memory,
+    /// Number of allocations of each part, recorded with the `alloc-metrics` feature.
+    Allocations,
+    /// Number of samples of each part.
+    Samples,
+    /// Share of the total time of the year, as a bar.
+    Share,
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "title" => Ok(Column::Title),
+            "stars" => Ok(Column::Stars),
+            "parse" => Ok(Column::Parse),
+            "memory" => Ok(Column::Memory),
+            "allocations" => Ok(Column::Allocations),
+            "samples" => Ok(Column::Samples),
+            "share" => Ok(Column::Share),
+            _ => Err(format!(
+                "unknown column `{s}`, expecting title, stars, parse, memory, allocations, samples or share."
+            )),
+        }
+    }
+}
+
+/// Layout of the benchmark table in the readme.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReadmeConfig {
+    /// Optional columns, always shown in the order of [`Column`]. `None` keeps the default layout.
+    pub columns: Option<Vec<Column>>,
+    /// Number of slowest days listed below a table.
+    pub slowest: Option<u64>,
+    /// Show a section for every year with stored timings instead of only the current one.
+    pub per_year: bool,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Config {
+    pub bench: BenchOverrides,
+    pub timeout: Timeouts,
+    /// Overrides of single days, read from `years.<year>.days`.
+    pub days: HashMap<(Year, Day), DayConfig>,
+    pub scaffold: ScaffoldConfig,
+    pub readme: ReadmeConfig,
+}
+
+impl Config {
+    /// Read the config file. If not present, returns the default config.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(CONFIG_FILE_PATH) {
+            Ok(s) => Config::try_from(s),
+            Err(_) => Ok(Config::default()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<String> for Config {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("config is not a valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected config to be an object.")?;
+
+        let bench = parse_bench(json.get("bench"), "config.bench")?;
+        let timeout = parse_timeouts(json.get("timeout"), "config.timeout")?;
+        let scaffold = parse_scaffold(json.get("scaffold"), "config.scaffold")?;
+        let readme = parse_readme(json.get("readme"), "config.readme")?;
+
+        if json.contains_key("days") {
+            // the same day of different years has a different puzzle.
+            return Err("config.days is set per year, move it to config.years.<year>.days.".into());
+        }
+
+        let mut days = HashMap::new();
+
+        if let Some(value) = json.get("years") {
+            let years = value
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected config.years to be an object.")?;
+
+            for (year_key, value) in years {
+                let year = Year::from_str(year_key)
+                    .map_err(|_| format!("expected config.years key `{year_key}` to be a year."))?;
+
+                let path = format!("config.years.{year_key}.days");
+                let entries = match value
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or(format!("expected config.years.{year_key} to be an object."))?
+                    .get("days")
+                {
+                    Some(v) => v
+                        .get::<HashMap<String, JsonValue>>()
+                        .ok_or(format!("expected {path} to be an object."))?,
+                    None => continue,
+                };
+
+                for (key, value) in entries {
+                    let day = Day::from_str(key)
+                        .ok()
+                        .filter(|day| day.into_inner() <= year.last_day())
+                        .ok_or(format!(
+                            "expected {path} key `{key}` to be a day of {year}."
+                        ))?;
+
+                    let day_json = value
+                        .get::<HashMap<String, JsonValue>>()
+                        .ok_or(format!("expected {path}.{key} to be an object."))?;
+
+                    let bench = parse_bench(day_json.get("bench"), &format!("{path}.{key}.bench"))?;
+                    let timeout =
+                        parse_timeouts(day_json.get("timeout"), &format!("{path}.{key}.timeout"))?;
+
+                    days.insert((year, day), DayConfig { bench, timeout });
+                }
+            }
+        }
+
+        Ok(Config {
+            bench,
+            timeout,
+            days,
+            scaffold,
+            readme,
+        })
+    }
+}
+
+fn parse_readme(value: Option<&JsonValue>, path: &str) -> Result<ReadmeConfig, String> {
+    let Some(value) = value else {
+        return Ok(ReadmeConfig::default());
+    };
+
+    let json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or(format!("expected {path} to be an object."))?;
+
+    let columns = match json.get("columns") {
+        None => None,
+        Some(v) => {
+            let values = v
+                .get::<Vec<JsonValue>>()
+                .ok_or(format!("expected {path}.columns to be an array."))?;
+
+            let mut columns = values
+                .iter()
+                .map(|v| {
+                    v.get::<String>()
+                        .ok_or(format!("expected {path}.columns to contain strings."))
+                        .and_then(|s| Column::from_str(s))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            columns.sort_unstable();
+            columns.dedup();
+            Some(columns)
+        }
+    };
+
+    let per_year = match json.get("per_year") {
+        None => false,
+        Some(v) => *v
+            .get::<bool>()
+            .ok_or(format!("expected {path}.per_year to be a boolean."))?,
+    };
+
+    Ok(ReadmeConfig {
+        columns,
+        slowest: parse_number(json, "slowest", path)?,
+        per_year,
+    })
+}
+
+fn parse_scaffold(value: Option<&JsonValue>, path: &str) -> Result<ScaffoldConfig, String> {
+    let Some(value) = value else {
+        return Ok(ScaffoldConfig::default());
+    };
+
+    let json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or(format!("expected {path} to be an object."))?;
+
+    let string = |key: &str| match json.get(key) {
+        None => Ok(None),
+        Some(v) => v
+            .get::<String>()
+            .map(|s| Some(s.clone()))
+            .ok_or(format!("expected {path}.{key} to be a string.")),
+    };
+
+    Ok(ScaffoldConfig {
+        template: string("template")?,
+        return_type: string("return_type")?,
+    })
+}
+
+fn parse_bench(value: Option<&JsonValue>, path: &str) -> Result<BenchOverrides, String> {
+    let Some(value) = value else {
+        return Ok(BenchOverrides::default());
+    };
+
+    let json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or(format!("expected {path} to be an object."))?;
+
+    let number = |key: &str| parse_number(json, key, path);
+
+    Ok(BenchOverrides {
+        warm_up: number("warm_up_ms")?.map(Duration::from_millis),
+        measurement_time: number("measurement_ms")?.map(Duration::from_millis),
+        min_samples: number("min_samples")?,
+        max_samples: number("max_samples")?,
+    })
+}
+
+fn parse_timeouts(value: Option<&JsonValue>, path: &str) -> Result<Timeouts, String> {
+    let Some(value) = value else {
+        return Ok(Timeouts::default());
+    };
+
+    let json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or(format!("expected {path} to be an object."))?;
+
+    Ok(Timeouts {
+        part: parse_number(json, "part_ms", path)?.map(Duration::from_millis),
+        day: parse_number(json, "day_ms", path)?.map(Duration::from_millis),
+    })
+}
+
+fn parse_number(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+    path: &str,
+) -> Result<Option<u64>, String> {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) => v
+            .get::<f64>()
+            .filter(|x| **x >= 0.0)
+            .map(|x| Some(*x as u64))
+            .ok_or(format!("expected {path}.{key} to be a positive number.")),
+    }
+}
+
+impl From<&BenchSettings> for JsonValue {
+    fn from(value: &BenchSettings) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "warm_up_ms".into(),
+                JsonValue::Number(value.warm_up.as_millis() as f64),
+            );
+            map.insert(
+                "measurement_ms".into(),
+                JsonValue::Number(value.measurement_time.as_millis() as f64),
+            );
+            map.insert(
+                "min_samples".into(),
+                JsonValue::Number(value.min_samples as f64),
+            );
+            map.insert(
+                "max_samples".into(),
+                JsonValue::Number(value.max_samples as f64),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchSettings {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let overrides = parse_bench(Some(value), "bench")?;
+
+        match overrides {
+            BenchOverrides {
+                warm_up: Some(warm_up),
+                measurement_time: Some(measurement_time),
+                min_samples: Some(min_samples),
+                max_samples: Some(max_samples),
+            } => Ok(BenchSettings {
+                warm_up,
+                measurement_time,
+                min_samples,
+                max_samples,
+            }),
+            _ => Err("Expected bench settings to have all keys.".into()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        BenchOverrides, BenchSettings, Column, Config, ReadmeConfig, ScaffoldConfig, Timeouts,
+    };
+    use crate::day;
+    use crate::template::Year;
+
+    fn year() -> Year {
+        Year::new(2024).unwrap()
+    }
+
+    fn get_mock_config() -> Config {
+        Config::try_from(
+            r#"{
+                "bench": { "warm_up_ms": 100, "max_samples": 500 },
+                "timeout": { "part_ms": 10000, "day_ms": 30000 },
+                "years": {
+                    "2024": {
+                        "days": {
+                            "06": { "bench": { "measurement_ms": 5000, "max_samples": 20 } },
+                            "17": { "timeout": { "part_ms": 1000 } }
+                        }
+                    }
+                },
+                "scaffold": { "template": "grid" }
+            }"#
+            .to_string(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn handles_empty_config() {
+        let config = Config::try_from("{}".to_string()).unwrap();
+        let settings = BenchSettings::resolve(&config, year(), day!(1), &BenchOverrides::default());
+        assert_eq!(settings, BenchSettings::default());
+    }
+
+    #[test]
+    fn reads_scaffold_config() {
+        assert_eq!(
+            get_mock_config().scaffold,
+            ScaffoldConfig {
+                template: Some("grid".into()),
+                return_type: None,
+            }
+        );
+        assert!(Config::try_from(r#"{ "scaffold": { "template": 1 } }"#.to_string()).is_err());
+    }
+
+    #[test]
+    fn reads_readme_config() {
+        assert_eq!(get_mock_config().readme, ReadmeConfig::default());
+
+        let config = Config::try_from(
+            r#"{ "readme": { "columns": ["share", "title", "share"], "slowest": 3, "per_year": true } }"#
+                .to_string(),
+        )
+        .unwrap();
+        assert_eq!(
+            config.readme,
+            ReadmeConfig {
+                columns: Some(vec![Column::Title, Column::Share]),
+                slowest: Some(3),
+                per_year: true,
+            }
+        );
+
+        assert!(
+            Config::try_from(r#"{ "readme": { "columns": ["speed"] } }"#.to_string())
+                .unwrap_err()
+                .contains("unknown column `speed`")
+        );
+    }
+
+    #[test]
+    fn applies_global_config() {
+        let settings = BenchSettings::resolve(
+            &get_mock_config(),
+            year(),
+            day!(1),
+            &BenchOverrides::default(),
+        );
+        assert_eq!(settings.warm_up, Duration::from_millis(100));
+        assert_eq!(settings.measurement_time, Duration::from_secs(1));
+        assert_eq!(settings.min_samples, 10);
+        assert_eq!(settings.max_samples, 500);
+    }
+
+    #[test]
+    fn applies_day_config() {
+        let settings = BenchSettings::resolve(
+            &get_mock_config(),
+            year(),
+            day!(6),
+            &BenchOverrides::default(),
+        );
+        assert_eq!(settings.warm_up, Duration::from_millis(100));
+        assert_eq!(settings.measurement_time, Duration::from_secs(5));
+        assert_eq!(settings.max_samples, 20);
+    }
+
+    #[test]
+    fn applies_arguments_last() {
+        let args = BenchOverrides {
+            max_samples: Some(50),
+            min_samples: Some(100),
+            ..BenchOverrides::default()
+        };
+        let settings = BenchSettings::resolve(&get_mock_config(), year(), day!(6), &args);
+        assert_eq!(settings.min_samples, 100);
+        assert_eq!(settings.max_samples, 100);
+    }
+
+    #[test]
+    fn mirrors_arguments() {
+        let args = BenchOverrides {
+            warm_up: Some(Duration::from_millis(250)),
+            max_samples: Some(50),
+            ..BenchOverrides::default()
+        };
+        assert_eq!(
+            args.to_args(),
+            vec!["--warm-up", "250", "--max-samples", "50"]
+        );
+    }
+
+    #[test]
+    fn resolves_timeouts() {
+        let config = get_mock_config();
+
+        let timeouts = Timeouts::resolve(&config, year(), day!(1), &Timeouts::default());
+        assert_eq!(timeouts.part, Some(Duration::from_secs(10)));
+        assert_eq!(timeouts.day, Some(Duration::from_secs(30)));
+
+        let timeouts = Timeouts::resolve(&config, year(), day!(17), &Timeouts::default());
+        assert_eq!(timeouts.part, Some(Duration::from_secs(1)));
+        assert_eq!(timeouts.day, Some(Duration::from_secs(30)));
+
+        let args = Timeouts {
+            day: Some(Duration::from_secs(5)),
+            ..Timeouts::default()
+        };
+        let timeouts = Timeouts::resolve(&config, year(), day!(17), &args);
+        assert_eq!(timeouts.part, Some(Duration::from_secs(1)));
+        assert_eq!(timeouts.day, Some(Duration::from_secs(5)));
+        assert_eq!(timeouts.to_args(), vec!["--timeout", "1000"]);
+    }
+
+    #[test]
+    fn handles_missing_timeouts() {
+        let config = Config::try_from("{}".to_string()).unwrap();
+        let timeouts = Timeouts::resolve(&config, year(), day!(1), &Timeouts::default());
+        assert_eq!(timeouts, Timeouts::default());
+        assert!(timeouts.to_args().is_empty());
+    }
+
+    #[test]
+    fn scopes_day_config_by_year() {
+        let other_year = Year::new(2023).unwrap();
+        let settings = BenchSettings::resolve(
+            &get_mock_config(),
+            other_year,
+            day!(6),
+            &BenchOverrides::default(),
+        );
+        assert_eq!(settings.measurement_time, Duration::from_secs(1));
+        assert_eq!(settings.max_samples, 500);
+
+        let timeouts = Timeouts::resolve(
+            &get_mock_config(),
+            other_year,
+            day!(17),
+            &Timeouts::default(),
+        );
+        assert_eq!(timeouts.part, Some(Duration::from_secs(10)));
+
+        // day overrides without a year are rejected.
+        assert!(Config::try_from(r#"{ "days": { "06": {} } }"#.to_string())
+            .unwrap_err()
+            .contains("config.years.<year>.days"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_days() {
+        Config::try_from(r#"{ "years": { "2025": { "days": { "13": {} } } } }"#.to_string())
+            .unwrap();
+    }
+}
@@ -5,10 +5,13 @@ use std::str::FromStr;
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
+use crate::template::Year;
+
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// Events from 2025 onward end on the 12th, see [`Year::has_day`].
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -38,18 +41,19 @@ impl Day {
     }
 
     /// Converts the [`Day`] into an [`u8`].
-    pub fn into_inner(self) -> u8 {
+    pub const fn into_inner(self) -> u8 {
         self.0
     }
 }
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's between the 1st and the last day of this year's event, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
+        let last_day = Year::today()?.last_day();
+        if today.month() == 12 && today.day() <= u32::from(last_day) {
             Self::new(u8::try_from(today.day()).ok()?)
         } else {
             None
@@ -100,20 +104,23 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
+/// An iterator that yields every day of advent of a year, from the 1st to the last day of its event.
+pub fn all_days(year: Year) -> AllDays {
+    AllDays::new(year)
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent of a year, from the 1st to the last day of its event.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    pub fn new(year: Year) -> Self {
+        Self {
+            current: 1,
+            last: year.last_day(),
+        }
     }
 }
 
@@ -121,10 +128,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the last day (at most 25).
         let day = Day(self.current);
         self.current += 1;
 
@@ -148,6 +155,18 @@ macro_rules! day {
         );
         $crate::template::Day::__new_unchecked($day)
     }};
+    // checks that the day is part of the event of a (const) year.
+    ($day:expr, $year:expr) => {{
+        const _ASSERT_YEAR: () = assert!(
+            $day <= $year.last_day(),
+            concat!(
+                "invalid day number `",
+                $day,
+                "`, the event of this year has fewer days"
+            ),
+        );
+        $crate::day!($day)
+    }};
 }
 
 /* -------------------------------------------------------------------------- */
@@ -155,10 +174,11 @@ macro_rules! day {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{all_days, Day};
+    use crate::template::Year;
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = all_days(Year::new(2024).unwrap());
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -187,6 +207,13 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_iterator_short_event() {
+        let days: Vec<Day> = all_days(Year::new(2025).unwrap()).collect();
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.last(), Some(&Day(12)));
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,444 @@
+/// Exports stored timings for use outside of the readme: as CSV or JSON for spreadsheets and scripts,
+/// or as a bar chart of the median time of every part. The chart uses a log scale, so that days in the
+/// nanosecond and in the second range fit next to each other, and needs no network resources.
+use std::fmt::Display;
+use std::str::FromStr;
+
+use tinyjson::JsonValue;
+
+use crate::template::alloc::AllocStats;
+use crate::template::statistics::Statistics;
+use crate::template::timings::{format_nanos, Timing, Timings};
+use crate::template::Year;
+
+/// Format of `cargo time --export <format>`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+    Html,
+    Svg,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            "html" => Ok(Format::Html),
+            "svg" => Ok(Format::Svg),
+            _ => Err(format!(
+                "unknown export format `{s}`, expecting csv, json, html or svg."
+            )),
+        }
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Format::Csv => "csv",
+            Format::Json => "json",
+            Format::Html => "html",
+            Format::Svg => "svg",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Render the most recent timing of every day of `year` in `format`.
+pub fn render(year: Year, timings: &Timings, format: Format) -> Result<String, String> {
+    let timings = of_year(year, timings);
+
+    match format {
+        Format::Csv => Ok(csv(year, &timings)),
+        Format::Json => JsonValue::from(timings)
+            .format()
+            .map_err(|e| format!("could not serialize timings: {e}")),
+        Format::Html => Ok(html(year, &timings)),
+        Format::Svg => Ok(svg(year, &timings)),
+    }
+}
+
+/// The most recent timing of every day that is part of the event of `year`, ordered by day.
+fn of_year(year: Year, timings: &Timings) -> Timings {
+    let mut data: Vec<Timing> = timings
+        .latest()
+        .data
+        .into_iter()
+        .filter(|t| year.has_day(t.day))
+        .collect();
+    data.sort_by_key(|t| t.day);
+    Timings { data }
+}
+
+/// A measured step of a day: the parse step or one of the parts.
+struct Bar<'a> {
+    label: &'static str,
+    color: &'static str,
+    nanos: u64,
+    stats: Option<&'a Statistics>,
+    alloc: Option<AllocStats>,
+}
+
+fn bars(timing: &Timing) -> Vec<Bar<'_>> {
+    [
+        (
+            "parse",
+            "#9e9e9e",
+            timing.parse,
+            &timing.parse_stats,
+            timing.parse_alloc,
+        ),
+        (
+            "1",
+            "#4e79a7",
+            timing.part_1,
+            &timing.part_1_stats,
+            timing.part_1_alloc,
+        ),
+        (
+            "2",
+            "#f28e2b",
+            timing.part_2,
+            &timing.part_2_stats,
+            timing.part_2_alloc,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(label, color, nanos, stats, alloc)| {
+        nanos.map(|nanos| Bar {
+            label,
+            color,
+            nanos,
+            stats: stats.as_ref(),
+            alloc,
+        })
+    })
+    .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One row per measured part, with its median and, if recorded, the full statistics and allocations.
+fn csv(year: Year, timings: &Timings) -> String {
+    let mut lines = vec![
+        "year,day,part,median_nanos,min_nanos,mean_nanos,p95_nanos,stddev_nanos,samples,alloc_bytes,alloc_count,peak_bytes,commit,label"
+            .to_string(),
+    ];
+
+    for timing in &timings.data {
+        let meta = timing.meta.as_ref();
+        let commit = meta.and_then(|m| m.commit.as_deref()).unwrap_or_default();
+        let label = meta.and_then(|m| m.label.as_deref()).unwrap_or_default();
+
+        for bar in bars(timing) {
+            let stats = bar.stats.map_or_else(
+                || ",,,,".to_string(),
+                |s| {
+                    format!(
+                        "{:.0},{:.0},{:.0},{:.0},{}",
+                        s.min, s.mean, s.p95, s.stddev, s.samples
+                    )
+                },
+            );
+
+            let alloc = bar.alloc.map_or_else(
+                || ",,".to_string(),
+                |a| format!("{},{},{}", a.bytes, a.count, a.peak_bytes),
+            );
+
+            lines.push(format!(
+                "{year},{},{},{},{stats},{alloc},{},{}",
+                timing.day,
+                bar.label,
+                bar.nanos,
+                csv_field(commit),
+                csv_field(label),
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/// Quote a field if it contains characters with a meaning in CSV.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+const WIDTH: f64 = 720.0;
+const LABEL_WIDTH: f64 = 64.0;
+const MARGIN: f64 = 16.0;
+const HEADER_HEIGHT: f64 = 56.0;
+const AXIS_HEIGHT: f64 = 32.0;
+const BAR_HEIGHT: f64 = 12.0;
+const BAR_GAP: f64 = 2.0;
+const DAY_GAP: f64 = 10.0;
+
+/// Horizontal bars of the median time of every part, grouped by day, on a log scale.
+fn svg(year: Year, timings: &Timings) -> String {
+    let days: Vec<(&Timing, Vec<Bar>)> = timings
+        .data
+        .iter()
+        .map(|t| (t, bars(t)))
+        .filter(|(_, bars)| !bars.is_empty())
+        .collect();
+
+    let chart_height: f64 = days
+        .iter()
+        .map(|(_, bars)| bars.len() as f64 * (BAR_HEIGHT + BAR_GAP) + DAY_GAP)
+        .sum();
+    let height = HEADER_HEIGHT + chart_height.max(BAR_HEIGHT) + AXIS_HEIGHT;
+    let chart_left = LABEL_WIDTH;
+    let chart_width = WIDTH - LABEL_WIDTH - MARGIN;
+
+    let mut out = vec![
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{WIDTH}" height="{height}" viewBox="0 0 {WIDTH} {height}" font-family="monospace" font-size="11">"#
+        ),
+        format!(r##"<rect width="{WIDTH}" height="{height}" fill="#ffffff"/>"##),
+        format!(
+            r#"<text x="{MARGIN}" y="20" font-size="14" font-weight="bold">Advent of Code {year}: median time per part (log scale)</text>"#
+        ),
+    ];
+
+    for (i, (label, color)) in [
+        ("Parse", "#9e9e9e"),
+        ("Part 1", "#4e79a7"),
+        ("Part 2", "#f28e2b"),
+    ]
+    .iter()
+    .enumerate()
+    {
+        let x = chart_left + i as f64 * 90.0;
+        out.push(format!(
+            r#"<rect x="{x}" y="30" width="10" height="10" fill="{color}"/><text x="{}" y="39">{label}</text>"#,
+            x + 14.0
+        ));
+    }
+
+    let all_nanos = days
+        .iter()
+        .flat_map(|(_, bars)| bars.iter().map(|b| b.nanos.max(1)));
+    let (Some(min), Some(max)) = (all_nanos.clone().min(), all_nanos.max()) else {
+        out.push(format!(
+            r#"<text x="{chart_left}" y="{}">No timings stored.</text>"#,
+            HEADER_HEIGHT + BAR_HEIGHT
+        ));
+        out.push("</svg>".into());
+        return out.join("\n");
+    };
+
+    let (low, high) = decades(min, max);
+    let scale = |nanos: u64| {
+        let position = ((nanos.max(1) as f64).log10() - f64::from(low)) / f64::from(high - low);
+        chart_left + position * chart_width
+    };
+
+    let axis_y = HEADER_HEIGHT + chart_height;
+    for decade in low..=high {
+        let nanos = 10_u64.pow(decade);
+        let x = scale(nanos);
+        out.push(format!(
+            r##"<line x1="{x:.1}" y1="{HEADER_HEIGHT}" x2="{x:.1}" y2="{axis_y}" stroke="#e0e0e0"/><text x="{x:.1}" y="{}" text-anchor="middle">{}</text>"##,
+            axis_y + 16.0,
+            format_nanos(nanos)
+        ));
+    }
+
+    let mut y = HEADER_HEIGHT;
+    for (timing, bars) in &days {
+        let group_height = bars.len() as f64 * (BAR_HEIGHT + BAR_GAP);
+        out.push(format!(
+            r#"<text x="{}" y="{:.1}" text-anchor="end" dominant-baseline="middle">Day {}</text>"#,
+            chart_left - 8.0,
+            y + group_height / 2.0,
+            timing.day
+        ));
+
+        for bar in bars {
+            let part = match bar.label {
+                "parse" => "Parse".to_string(),
+                part => format!("Part {part}"),
+            };
+            out.push(format!(
+                r#"<rect x="{chart_left}" y="{y:.1}" width="{:.1}" height="{BAR_HEIGHT}" fill="{}"><title>Day {} {part}: {}</title></rect>"#,
+                (scale(bar.nanos) - chart_left).max(1.0),
+                bar.color,
+                timing.day,
+                format_nanos(bar.nanos)
+            ));
+            y += BAR_HEIGHT + BAR_GAP;
+        }
+
+        y += DAY_GAP;
+    }
+
+    out.push(format!(
+        r##"<line x1="{chart_left}" y1="{HEADER_HEIGHT}" x2="{chart_left}" y2="{axis_y}" stroke="#757575"/>"##
+    ));
+    out.push("</svg>".into());
+    out.join("\n")
+}
+
+/// Powers of ten that enclose `min` and `max`, at least one decade apart.
+fn decades(min: u64, max: u64) -> (u32, u32) {
+    let low = min.ilog10();
+    let high = if max == 10_u64.pow(max.ilog10()) {
+        max.ilog10()
+    } else {
+        max.ilog10() + 1
+    };
+    (low, high.max(low + 1))
+}
+
+/// A standalone page with the chart inlined.
+fn html(year: Year, timings: &Timings) -> String {
+    [
+        "<!DOCTYPE html>".to_string(),
+        r#"<html lang="en">"#.into(),
+        "<head>".into(),
+        r#"<meta charset="utf-8">"#.into(),
+        format!("<title>Advent of Code {year} benchmarks</title>"),
+        "</head>".into(),
+        r#"<body style="margin: 2rem; font-family: sans-serif;">"#.into(),
+        svg(year, timings),
+        "</body>".into(),
+        "</html>".into(),
+        String::new(),
+    ]
+    .join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{decades, render, Format};
+    use crate::{
+        day,
+        template::alloc::AllocStats,
+        template::statistics::Statistics,
+        template::timings::{Timing, TimingMeta, Timings},
+        template::{Day, Year},
+    };
+
+    fn get_mock_timing(day: u8, part_1: Option<u64>, part_2: Option<u64>) -> Timing {
+        Timing {
+            part_1,
+            part_2,
+            ..Timing::new(Day::new(day).unwrap())
+        }
+    }
+
+    fn get_mock_timings() -> Timings {
+        let mut first = get_mock_timing(1, Some(120), Some(3_500_000));
+        first.part_1_stats = Some(Statistics {
+            min: 100.0,
+            median: 120.0,
+            mean: 121.4,
+            p95: 140.0,
+            stddev: 9.6,
+            samples: 10_000,
+            outliers: 3,
+        });
+        first.part_1_alloc = Some(AllocStats {
+            bytes: 4096,
+            count: 12,
+            peak_bytes: 1024,
+        });
+        first.meta = Some(TimingMeta {
+            timestamp: 1,
+            commit: Some("abc1234".into()),
+            host: None,
+            label: Some("before, refactor".into()),
+        });
+
+        Timings {
+            data: vec![
+                get_mock_timing(2, Some(1_000), None),
+                get_mock_timing(1, Some(999), None),
+                first,
+                get_mock_timing(13, Some(1), Some(1)),
+            ],
+        }
+    }
+
+    fn get_mock_year() -> Year {
+        Year::new(2025).unwrap()
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("csv".parse::<Format>(), Ok(Format::Csv));
+        assert_eq!("SVG".parse::<Format>(), Ok(Format::Svg));
+        assert!("png".parse::<Format>().unwrap_err().contains("png"));
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = render(get_mock_year(), &get_mock_timings(), Format::Csv).unwrap();
+        let expected = [
+            "year,day,part,median_nanos,min_nanos,mean_nanos,p95_nanos,stddev_nanos,samples,alloc_bytes,alloc_count,peak_bytes,commit,label",
+            "2025,01,1,120,100,121,140,10,10000,4096,12,1024,abc1234,\"before, refactor\"",
+            "2025,01,2,3500000,,,,,,,,,abc1234,\"before, refactor\"",
+            "2025,02,1,1000,,,,,,,,,,",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(csv, expected);
+    }
+
+    #[test]
+    fn exports_json() {
+        let json = render(get_mock_year(), &get_mock_timings(), Format::Json).unwrap();
+        let timings = Timings::try_from(json).unwrap();
+        assert_eq!(timings.data.len(), 2);
+        assert_eq!(timings.data[0].day, day!(1));
+        assert_eq!(timings.data[0].part_1, Some(120));
+    }
+
+    #[test]
+    fn exports_svg_chart() {
+        let svg = render(get_mock_year(), &get_mock_timings(), Format::Svg).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>"));
+        assert!(svg.contains("<title>Day 01 Part 2: 3.5ms</title>"));
+        assert!(svg.contains("<title>Day 02 Part 1: 1.0µs</title>"));
+        assert!(!svg.contains("Day 13"));
+        // ticks every decade from 100ns to 10ms.
+        assert!(svg.contains(">100.0ns</text>"));
+        assert!(svg.contains(">10.0ms</text>"));
+        assert!(!svg.contains(">10.0ns</text>"));
+    }
+
+    #[test]
+    fn exports_empty_svg_chart() {
+        let svg = render(get_mock_year(), &Timings::default(), Format::Svg).unwrap();
+        assert!(svg.contains("No timings stored."));
+    }
+
+    #[test]
+    fn exports_html_page() {
+        let html = render(get_mock_year(), &get_mock_timings(), Format::Html).unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<svg"));
+        assert!(!html.contains("<script") && !html.contains("<link"));
+    }
+
+    #[test]
+    fn spans_whole_decades() {
+        assert_eq!(decades(120, 3_500_000), (2, 7));
+        assert_eq!(decades(1, 1), (0, 1));
+        assert_eq!(decades(1_000, 1_000), (3, 4));
+        assert_eq!(decades(999, 1_000), (2, 3));
+    }
+}
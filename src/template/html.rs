@@ -0,0 +1,198 @@
+/// Minimal conversion of advent of code puzzle HTML to markdown.
+/// Only handles the handful of tags that appear in puzzle descriptions.
+///
+/// Returns the inner HTML of every `<article>` element, in order.
+pub fn articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(open_end) = rest[start..].find('>') else {
+            break;
+        };
+        let content_start = start + open_end + 1;
+        let Some(len) = rest[content_start..].find("</article>") else {
+            break;
+        };
+
+        articles.push(&rest[content_start..content_start + len]);
+        rest = &rest[content_start + len..];
+    }
+
+    articles
+}
+
+/// Converts an HTML fragment to markdown.
+/// `<code><em>` is written as `` `*x*` ``, preformatted blocks become fenced code blocks.
+pub fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut links: Vec<String> = vec![];
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            push_text(&mut out, rest, in_pre);
+            break;
+        };
+
+        push_text(&mut out, &rest[..tag_start], in_pre);
+
+        let Some(tag_len) = rest[tag_start..].find('>') else {
+            break;
+        };
+        let tag = Tag::parse(&rest[tag_start + 1..tag_start + tag_len]);
+        rest = &rest[tag_start + tag_len + 1..];
+
+        match (tag.name.as_str(), tag.is_closing) {
+            ("pre", false) => {
+                out.push_str("```\n");
+                in_pre = true;
+            }
+            ("pre", true) => {
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+                in_pre = false;
+            }
+            // preformatted blocks are kept as-is, so examples can be copied from them.
+            (_, _) if in_pre => {}
+            ("h2", false) => out.push_str("## "),
+            ("h2" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("li", false) => out.push_str("* "),
+            ("li" | "br", _) => out.push('\n'),
+            ("code", _) => out.push('`'),
+            ("em", _) => out.push('*'),
+            ("a", false) => {
+                out.push('[');
+                links.push(tag.href.unwrap_or_default());
+            }
+            ("a", true) => {
+                out.push_str(&format!("]({})", links.pop().unwrap_or_default()));
+            }
+            _ => {}
+        }
+    }
+
+    // normalize the spacing between blocks.
+    let mut markdown = out.trim().to_string();
+    while markdown.contains("\n\n\n") {
+        markdown = markdown.replace("\n\n\n", "\n\n");
+    }
+    markdown.push('\n');
+    markdown
+}
+
+/// Returns the text content of an HTML fragment, i.e. without tags and with collapsed whitespace.
+pub fn to_text(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+
+    while let Some(tag_start) = rest.find('<') {
+        push_text(&mut out, &rest[..tag_start], false);
+        match rest[tag_start..].find('>') {
+            Some(tag_len) => rest = &rest[tag_start + tag_len + 1..],
+            None => rest = "",
+        }
+    }
+    push_text(&mut out, rest, false);
+
+    out.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let text = decode_entities(text);
+    if in_pre {
+        out.push_str(&text);
+    } else if out.is_empty() || out.ends_with('\n') {
+        // whitespace between blocks is not part of the content.
+        out.push_str(text.replace('\n', " ").trim_start());
+    } else {
+        out.push_str(&text.replace('\n', " "));
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+struct Tag {
+    name: String,
+    is_closing: bool,
+    href: Option<String>,
+}
+
+impl Tag {
+    fn parse(s: &str) -> Self {
+        let is_closing = s.starts_with('/');
+        let s = s.trim_start_matches('/');
+
+        let name = s
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        let href = s.find("href=\"").and_then(|start| {
+            let value = &s[start + 6..];
+            value.find('"').map(|end| decode_entities(&value[..end]))
+        });
+
+        Tag {
+            name,
+            is_closing,
+            href,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_articles() {
+        let html = r#"<main><article class="day-desc"><h2>Part 1</h2></article><p>Your puzzle answer was <code>1</code>.</p><article class="day-desc"><h2 id="part2">Part 2</h2></article></main>"#;
+        assert_eq!(
+            articles(html),
+            vec!["<h2>Part 1</h2>", r#"<h2 id="part2">Part 2</h2>"#]
+        );
+    }
+
+    #[test]
+    fn converts_puzzle_markup() {
+        let html = r#"<h2>--- Day 1: Historian Hysteria ---</h2><p>Read the <a href="/2024/about">about page</a> &amp; the list:</p>
+<pre><code>3   4
+4   3
+<em>1</em>   &lt;
+</code></pre>
+<ul><li>In the example, the total is <code><em>11</em></code>.</li></ul>
+<p>What is the <em>total distance</em>?</p>"#;
+
+        assert_eq!(
+            to_markdown(html),
+            "## --- Day 1: Historian Hysteria ---\n\n\
+             Read the [about page](/2024/about) & the list:\n\n\
+             ```\n3   4\n4   3\n1   <\n```\n\n\
+             * In the example, the total is `*11*`.\n\n\
+             What is the *total distance*?\n"
+        );
+    }
+
+    #[test]
+    fn extracts_text() {
+        assert_eq!(
+            to_text("<p>That's not the right answer;\n  your answer is <em>too high</em>.</p>"),
+            "That's not the right answer; your answer is too high."
+        );
+    }
+}
@@ -0,0 +1,143 @@
+/// Renders puzzle markdown, as written by `download`, for the terminal.
+/// Emphasis (advent of code's highlights) is shown in bold and inline code in italics.
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Renders markdown with ANSI escape codes. Code blocks are indented and kept as-is.
+pub fn to_ansi(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut in_block = false;
+
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_block = !in_block;
+            continue;
+        }
+
+        if in_block {
+            out.push_str("    ");
+            out.push_str(line);
+        } else if let Some(heading) = line.strip_prefix("## ") {
+            out.push_str(&format!("{ANSI_BOLD}{heading}{ANSI_RESET}"));
+        } else if let Some(item) = line.strip_prefix("* ") {
+            out.push_str("  • ");
+            out.push_str(&inline(item));
+        } else {
+            out.push_str(&inline(line));
+        }
+
+        out.push('\n');
+    }
+
+    out
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+struct Style {
+    bold: bool,
+    italic: bool,
+}
+
+impl Style {
+    fn to_ansi(self) -> String {
+        let mut codes = ANSI_RESET.to_string();
+        if self.bold {
+            codes.push_str(ANSI_BOLD);
+        }
+        if self.italic {
+            codes.push_str(ANSI_ITALIC);
+        }
+        codes
+    }
+}
+
+/// Renders the emphasis and code spans of a line. Links are replaced by their text.
+fn inline(line: &str) -> String {
+    let text = strip_links(line);
+    let mut out = String::new();
+    let mut style = Style::default();
+
+    for (i, c) in text.char_indices() {
+        match c {
+            '`' => style.italic = !style.italic,
+            // a lone `*` is not emphasis.
+            '*' if style.bold || text[i + 1..].contains('*') => style.bold = !style.bold,
+            c => {
+                out.push(c);
+                continue;
+            }
+        }
+        out.push_str(&style.to_ansi());
+    }
+
+    if style != Style::default() {
+        out.push_str(ANSI_RESET);
+    }
+
+    out
+}
+
+/// Replaces links like `[text](href)` by their text.
+fn strip_links(line: &str) -> String {
+    let mut out = String::new();
+    let mut rest = line;
+
+    while let Some(start) = rest.find('[') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        let link = after.find("](").and_then(|label_end| {
+            let label = &after[..label_end];
+            let href_len = after[label_end..].find(')')?;
+            (!label.contains('[')).then_some((label, label_end + href_len + 1))
+        });
+
+        match link {
+            Some((label, len)) => {
+                out.push_str(label);
+                rest = &after[len..];
+            }
+            None => {
+                out.push('[');
+                rest = after;
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_blocks() {
+        let markdown = "## --- Day 1: Test ---\n\nThe list:\n\n```\n3 *4*\n```\n\n* an item\n";
+        assert_eq!(
+            to_ansi(markdown),
+            format!("{ANSI_BOLD}--- Day 1: Test ---{ANSI_RESET}\n\nThe list:\n\n    3 *4*\n\n  • an item\n")
+        );
+    }
+
+    #[test]
+    fn renders_emphasis_and_code() {
+        assert_eq!(
+            inline("a *total* of `*11*`"),
+            format!(
+                "a {ANSI_RESET}{ANSI_BOLD}total{ANSI_RESET} of {ANSI_RESET}{ANSI_ITALIC}{ANSI_RESET}{ANSI_BOLD}{ANSI_ITALIC}11{ANSI_RESET}{ANSI_ITALIC}{ANSI_RESET}"
+            )
+        );
+        assert_eq!(inline("2 * 3"), "2 * 3");
+    }
+
+    #[test]
+    fn replaces_links_by_their_text() {
+        assert_eq!(
+            strip_links("Read the [about page](/2024/about) and [this]."),
+            "Read the about page and [this]."
+        );
+    }
+}
@@ -1,44 +1,82 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod alloc;
+pub mod answers;
+pub mod aoc_client;
 pub mod commands;
+pub mod config;
+pub mod puzzle;
+pub mod rate_limit;
+pub mod report;
 pub mod runner;
+pub mod statistics;
+pub mod submissions;
 
 pub use day::*;
+pub use year::*;
 
 mod day;
+mod export;
+mod html;
+mod markdown;
 mod readme_benchmarks;
 mod run_multi;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file of a year to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, year: Year, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(get_data_path(year, &format!("{folder}/{day}.txt")));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
+/// Helper function that reads a text file of a year to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, year: Year, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
+    let filepath = cwd.join(get_data_path(year, &format!("{folder}/{day}-{part}.txt")));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Path of a data file (input, example, puzzle, timings) of a year, e.g. `data/2024/inputs/01.txt`.
+#[must_use]
+pub fn get_data_path(year: Year, path: &str) -> String {
+    format!("data/{year}/{path}")
+}
+
+/// Name of the solution bin of a day, e.g. `2024-01`.
+#[must_use]
+pub fn get_bin_name(year: Year, day: Day) -> String {
+    format!("{year}-{day}")
+}
+
+/// Path of the solution file of a day, e.g. `src/bin/2024-01.rs`.
+#[must_use]
+pub fn get_bin_path(year: Year, day: Day) -> String {
+    format!("src/bin/{}.rs", get_bin_name(year, day))
+}
+
+/// Creates the constants `YEAR` and `DAY` and sets up the input and runner for each part.
+/// The year is read from the name of the solution file, e.g. `src/bin/2024-01.rs`.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// The optional `parse = <function>` parameter sets up a parse step that runs once. Its result is passed
+/// to the parts by reference, and it is timed separately from them:
+/// ```ignore
+/// advent_of_code::solution!(1, parse = parse_input);
+///
+/// fn parse_input(input: &str) -> Vec<u32> { todo!() }
+/// pub fn part_one(input: &Vec<u32>) -> Option<u32> { todo!() }
+/// ```
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -50,19 +88,87 @@ macro_rules! solution {
     ($day:expr, 2) => {
         $crate::solution!(@impl $day, [part_two, 2]);
     };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@parsed $day, $parse, [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, parse = $parse:expr) => {
+        $crate::solution!(@parsed $day, $parse, [part_one, 1]);
+    };
+    ($day:expr, 2, parse = $parse:expr) => {
+        $crate::solution!(@parsed $day, $parse, [part_two, 2]);
+    };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day);
+
+        // Not part of the public API
+        #[doc(hidden)]
+        pub fn __run(
+            input: &str,
+            options: &$crate::template::runner::RunOptions,
+        ) -> Vec<$crate::template::report::PartReport> {
+            use $crate::template::runner::*;
+            let reports = vec![$( run_part($func, input, YEAR, DAY, $part, options), )*];
+            submit(&reports, YEAR, DAY, options, __example);
+            reports
+        }
+
+        fn __example(part: u8) -> Option<String> {
+            use $crate::template::runner::*;
+            let input = read_example(YEAR, DAY, part)?;
+            $( if part == $part { return example_answer(|| $func(&input)); } )*
+            None
+        }
+    };
+
+    (@parsed $day:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@setup $day);
+
+        // Not part of the public API
+        #[doc(hidden)]
+        pub fn __run(
+            input: &str,
+            options: &$crate::template::runner::RunOptions,
+        ) -> Vec<$crate::template::report::PartReport> {
+            use $crate::template::runner::*;
+            let reports = match run_parse($parse, input, YEAR, DAY, options) {
+                (Some(parsed), report) => vec![report, $( run_part($func, &parsed, YEAR, DAY, $part, options), )*],
+                // the parts can not run without their input.
+                (None, report) => vec![report],
+            };
+            submit(&reports, YEAR, DAY, options, __example);
+            reports
+        }
+
+        fn __example(part: u8) -> Option<String> {
+            use $crate::template::runner::*;
+            let input = read_example(YEAR, DAY, part)?;
+            $( if part == $part { return example_answer(|| $func(&$parse(&input))); } )*
+            None
+        }
+    };
+
+    (@setup $day:expr) => {
+        /// The year of the current solution.
+        const YEAR: $crate::template::Year = $crate::template::Year::__from_path(file!());
+
         /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        const DAY: $crate::template::Day = $crate::day!($day, YEAR);
+
+        fn main() {
+            let input = $crate::template::read_file("inputs", YEAR, DAY);
+            let reports = __run(&input, &$crate::template::runner::RunOptions::from_args());
+            $crate::template::runner::exit_with_status(&reports);
+        }
 
-        #[cfg(feature = "dhat-heap")]
+        // the registry includes all solutions in one binary, which can only have one global allocator.
+        #[cfg(all(feature = "dhat-heap", not(feature = "registry")))]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
-        fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
-        }
+        // DHAT takes precedence, it counts allocations itself.
+        #[cfg(all(feature = "alloc-metrics", not(feature = "dhat-heap"), not(feature = "registry")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc::CountingAlloc = $crate::template::alloc::CountingAlloc;
     };
 }
@@ -0,0 +1,274 @@
+/// Helpers that read downloaded puzzle descriptions, e.g. `data/2024/puzzles/01.md`.
+use std::fs;
+
+use crate::template::aoc_client::get_puzzle_path;
+use crate::template::{Day, Year};
+
+/// Read the puzzle description of a day, if it was downloaded.
+pub fn read(year: Year, day: Day) -> Option<String> {
+    fs::read_to_string(get_puzzle_path(year, day)).ok()
+}
+
+/// Returns the title of the puzzle, e.g. `Historian Hysteria` for `--- Day 1: Historian Hysteria ---`.
+pub fn title(markdown: &str) -> Option<&str> {
+    let heading = markdown.lines().find(|line| line.contains("--- Day "))?;
+    let (_, title) = heading.split_once(": ")?;
+    title.strip_suffix("---").map(str::trim)
+}
+
+/// Returns the description of part one, i.e. everything before part two.
+pub fn part_one(markdown: &str) -> &str {
+    part_two_start(markdown).map_or(markdown, |start| &markdown[..start])
+}
+
+/// Returns the description of part two, starting at its heading. `None` if part two is still locked.
+pub fn part_two(markdown: &str) -> Option<&str> {
+    part_two_start(markdown).map(|start| &markdown[start..])
+}
+
+/// The start of the line with the heading of part two.
+fn part_two_start(markdown: &str) -> Option<usize> {
+    let heading = markdown.find("--- Part Two ---")?;
+    Some(markdown[..heading].rfind('\n').map_or(0, |i| i + 1))
+}
+
+/// A code block of a puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The part whose description contains the block.
+    pub part: u8,
+    pub content: String,
+    /// The paragraph before the block.
+    pub context: String,
+}
+
+impl CodeBlock {
+    /// Blocks that are introduced as an example, e.g. "For example:", likely hold an example input.
+    pub fn is_example(&self) -> bool {
+        self.context.to_lowercase().contains("example")
+    }
+}
+
+/// Returns the code blocks of a puzzle description, in order.
+/// Handles fenced blocks as well as `<pre><code>` blocks.
+pub fn code_blocks(markdown: &str) -> Vec<CodeBlock> {
+    let mut blocks = vec![];
+    let mut part = 1;
+    let mut context = String::new();
+    // lines of the current block and whether it is a `<pre><code>` block.
+    let mut block: Option<(Vec<&str>, bool)> = None;
+
+    for line in markdown.lines() {
+        if let Some((lines, is_html)) = block.as_mut() {
+            let end = if *is_html {
+                line.trim_end().strip_suffix("</code></pre>")
+            } else {
+                (line.trim_end() == "```").then_some("")
+            };
+
+            if let Some(rest) = end {
+                if !rest.is_empty() {
+                    lines.push(rest);
+                }
+
+                blocks.push(CodeBlock {
+                    part,
+                    content: to_content(lines, *is_html),
+                    context: context.clone(),
+                });
+                block = None;
+            } else {
+                lines.push(line);
+            }
+            continue;
+        }
+
+        if line.starts_with("```") {
+            block = Some((vec![], false));
+        } else if let Some(rest) = line.strip_prefix("<pre><code>") {
+            match rest.strip_suffix("</code></pre>") {
+                Some(content) => blocks.push(CodeBlock {
+                    part,
+                    content: to_content(&[content], true),
+                    context: context.clone(),
+                }),
+                None if rest.is_empty() => block = Some((vec![], true)),
+                None => block = Some((vec![rest], true)),
+            }
+        } else if line.contains("--- Part Two ---") {
+            part = 2;
+            context.clear();
+        } else if !line.trim().is_empty() {
+            context = line.trim().to_string();
+        }
+    }
+
+    blocks
+}
+
+/// Returns the example answer of each part, i.e. the last emphasized code (`<code><em>`) in the part's description.
+/// Code blocks are skipped.
+pub fn example_answers(markdown: &str) -> [Option<String>; 2] {
+    let mut answers = [None, None];
+    let mut part = 1;
+    let mut in_block = false;
+
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_block = !in_block;
+        } else if in_block || line.starts_with("<pre><code>") {
+            in_block = !line.trim_end().ends_with("</code></pre>");
+        } else if line.contains("--- Part Two ---") {
+            part = 2;
+        } else if let Some(answer) = emphasized_code(line).pop() {
+            answers[part - 1] = Some(answer);
+        }
+    }
+
+    answers
+}
+
+/// Returns the emphasized code spans of a line. Accepts `` `*x*` ``, `` *`x`* `` and `<code><em>x</em></code>`.
+fn emphasized_code(line: &str) -> Vec<String> {
+    const DELIMITERS: [(&str, &str); 3] =
+        [("`*", "*`"), ("*`", "`*"), ("<code><em>", "</em></code>")];
+
+    let mut spans: Vec<(usize, String)> = vec![];
+
+    for (open, close) in DELIMITERS {
+        let mut offset = 0;
+        while let Some(start) = line[offset..].find(open) {
+            let content_start = offset + start + open.len();
+            let Some(len) = line[content_start..].find(close) else {
+                break;
+            };
+            let content = &line[content_start..content_start + len];
+            if !content.is_empty() {
+                spans.push((offset + start, content.to_string()));
+            }
+            offset = content_start + len + close.len();
+        }
+    }
+
+    spans.sort_by_key(|(pos, _)| *pos);
+    spans.into_iter().map(|(_, span)| span).collect()
+}
+
+/// Join the lines of a block. HTML entities in the content of `<pre><code>` blocks are decoded, so the result is plain text.
+fn to_content(lines: &[&str], is_html: bool) -> String {
+    let content = lines.join("\n");
+    let content = if is_html {
+        content
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&amp;", "&")
+    } else {
+        content
+    };
+    format!("{content}\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{code_blocks, example_answers, part_one, part_two, title};
+
+    const PUZZLE: &str = "## --- Day 1: Historian Hysteria ---
+
+Throughout the Chief's office, the historically significant locations are listed:
+
+```
+not an example
+```
+
+For example:
+
+```
+3   4
+4   3
+```
+
+Here is a larger example:
+
+<pre><code>1 &lt; 2
+3 4
+</code></pre>
+
+## --- Part Two ---
+
+For example, again:
+
+<pre><code>5 6</code></pre>
+";
+
+    #[test]
+    fn finds_code_blocks() {
+        let blocks = code_blocks(PUZZLE);
+
+        assert_eq!(blocks.len(), 4);
+
+        assert_eq!(blocks[0].part, 1);
+        assert_eq!(blocks[0].content, "not an example\n");
+        assert!(!blocks[0].is_example());
+
+        assert_eq!(blocks[1].content, "3   4\n4   3\n");
+        assert!(blocks[1].is_example());
+
+        assert_eq!(blocks[2].content, "1 < 2\n3 4\n");
+        assert!(blocks[2].is_example());
+
+        assert_eq!(blocks[3].part, 2);
+        assert_eq!(blocks[3].content, "5 6\n");
+        assert_eq!(blocks[3].context, "For example, again:");
+    }
+
+    #[test]
+    fn finds_title() {
+        assert_eq!(title(PUZZLE), Some("Historian Hysteria"));
+        assert_eq!(title("No heading"), None);
+    }
+
+    #[test]
+    fn finds_part_two() {
+        assert_eq!(
+            part_two(PUZZLE),
+            Some("## --- Part Two ---\n\nFor example, again:\n\n<pre><code>5 6</code></pre>\n")
+        );
+        assert_eq!(part_two("## --- Day 1: Test ---\n"), None);
+        assert!(part_one(PUZZLE).ends_with("</code></pre>\n\n"));
+        assert_eq!(
+            part_one("## --- Day 1: Test ---\n"),
+            "## --- Day 1: Test ---\n"
+        );
+    }
+
+    #[test]
+    fn finds_example_answers() {
+        let markdown = "## --- Day 1: Test ---
+
+In the example, the first pair has a distance of `*2*`.
+
+```
+`*1*`
+```
+
+The total distance is `*11*`. What is the total distance?
+
+## --- Part Two ---
+
+So, for these example lists, the similarity score is *`31`*.
+
+<pre><code>`*99*`
+</code></pre>
+";
+        assert_eq!(
+            example_answers(markdown),
+            [Some("11".to_string()), Some("31".to_string())]
+        );
+        assert_eq!(
+            example_answers("## --- Day 1: Test ---\n\nNo answers."),
+            [None, None]
+        );
+    }
+}
@@ -0,0 +1,164 @@
+/// Waits out the cooldown advent of code imposes between submissions.
+use std::io::{stdout, Write};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::template::aoc_client::SubmitOutcome;
+
+/// How often a rate-limited answer is resubmitted before giving up.
+const MAX_ATTEMPTS: usize = 5;
+
+/// Source of time for waiting. Replaced by a fake clock in tests.
+pub trait Clock {
+    /// Seconds since the unix epoch.
+    fn now(&self) -> u64;
+    fn sleep(&mut self, duration: Duration);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs())
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+/// Sleep for `duration` while counting down the remaining seconds.
+pub fn countdown(clock: &mut impl Clock, duration: Duration) {
+    let mut stdout = stdout();
+    let mut remaining = duration.as_secs();
+
+    while remaining > 0 {
+        print!("\rWaiting for the cooldown, submitting in {remaining}s... ");
+        let _ = stdout.flush();
+        clock.sleep(Duration::from_secs(1));
+        remaining -= 1;
+    }
+
+    println!("\rWaiting for the cooldown, submitting now.           ");
+}
+
+/// Submit an answer, waiting out the cooldown and resubmitting if the submission was rate-limited.
+/// `record` is called with the outcome of every attempt. With `no_wait`, a rate-limited outcome is returned right away.
+pub fn submit_with_retry<E>(
+    clock: &mut impl Clock,
+    no_wait: bool,
+    mut submit: impl FnMut() -> Result<SubmitOutcome, E>,
+    mut record: impl FnMut(&SubmitOutcome),
+) -> Result<SubmitOutcome, E> {
+    let mut attempts = 0;
+
+    loop {
+        let outcome = submit()?;
+        record(&outcome);
+        attempts += 1;
+
+        match outcome {
+            SubmitOutcome::RateLimited(wait) if !no_wait && attempts < MAX_ATTEMPTS => {
+                // the website rounds the wait down, add a second to be safe.
+                countdown(clock, wait + Duration::from_secs(1));
+            }
+            outcome => return Ok(outcome),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{submit_with_retry, Clock};
+    use crate::template::aoc_client::SubmitOutcome;
+
+    #[derive(Default)]
+    struct FakeClock {
+        now: u64,
+        sleeps: Vec<Duration>,
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> u64 {
+            self.now
+        }
+
+        fn sleep(&mut self, duration: Duration) {
+            self.now += duration.as_secs();
+            self.sleeps.push(duration);
+        }
+    }
+
+    fn run(
+        clock: &mut FakeClock,
+        no_wait: bool,
+        responses: Vec<SubmitOutcome>,
+    ) -> (SubmitOutcome, Vec<SubmitOutcome>) {
+        let mut responses = responses.into_iter();
+        let mut recorded = vec![];
+
+        let outcome = submit_with_retry(
+            clock,
+            no_wait,
+            || responses.next().ok_or("no more responses"),
+            |outcome| recorded.push(outcome.clone()),
+        )
+        .unwrap();
+
+        (outcome, recorded)
+    }
+
+    #[test]
+    fn waits_and_resubmits() {
+        let mut clock = FakeClock::default();
+
+        let (outcome, recorded) = run(
+            &mut clock,
+            false,
+            vec![
+                SubmitOutcome::RateLimited(Duration::from_secs(38)),
+                SubmitOutcome::Correct,
+            ],
+        );
+
+        assert_eq!(outcome, SubmitOutcome::Correct);
+        assert_eq!(recorded.len(), 2);
+        assert_eq!(clock.now, 39);
+        assert!(clock.sleeps.iter().all(|s| *s == Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn fails_fast_without_wait() {
+        let mut clock = FakeClock::default();
+
+        let (outcome, recorded) = run(
+            &mut clock,
+            true,
+            vec![SubmitOutcome::RateLimited(Duration::from_secs(38))],
+        );
+
+        assert_eq!(outcome, SubmitOutcome::RateLimited(Duration::from_secs(38)));
+        assert_eq!(recorded.len(), 1);
+        assert!(clock.sleeps.is_empty());
+    }
+
+    #[test]
+    fn gives_up_after_max_attempts() {
+        let mut clock = FakeClock::default();
+
+        let (outcome, recorded) = run(
+            &mut clock,
+            false,
+            vec![SubmitOutcome::RateLimited(Duration::from_secs(1)); 10],
+        );
+
+        assert_eq!(outcome, SubmitOutcome::RateLimited(Duration::from_secs(1)));
+        assert_eq!(recorded.len(), 5);
+    }
+}
@@ -1,11 +1,18 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{collections::HashMap, fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::alloc::AllocStats;
+use crate::template::answers::Answers;
+use crate::template::aoc_client::get_puzzle_path;
+use crate::template::config::{Column, Config, ReadmeConfig};
+use crate::template::export::{self, Format};
+use crate::template::statistics::Statistics;
+use crate::template::timings::{format_bytes, format_nanos, stored_years, Timings};
+use crate::template::{get_bin_path, puzzle, Day, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static CHART_ALT: &str = "Benchmarks chart";
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -25,11 +32,6 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     let matches: Vec<_> = readme.match_indices(MARKER).collect();
 
@@ -52,107 +54,397 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Path of the chart that is embedded in the benchmark table, if any.
+fn find_chart(table: &str) -> Option<String> {
+    let prefix = format!("![{CHART_ALT}](");
+    table
+        .lines()
+        .find_map(|line| line.strip_prefix(&prefix)?.strip_suffix(')'))
+        .map(String::from)
+}
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+/// Timings of a year and the puzzle data shown next to them.
+struct Section {
+    year: Year,
+    /// Most recent timing of every day of the year.
+    timings: Timings,
+    titles: HashMap<Day, String>,
+    stars: HashMap<Day, usize>,
+}
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+impl Section {
+    fn new(year: Year, timings: &Timings) -> Self {
+        let data = timings
+            .latest()
+            .data
+            .into_iter()
+            // stored timings may include days that are not part of the event of this year.
+            .filter(|t| year.has_day(t.day))
+            .collect();
+
+        Section {
+            year,
+            timings: Timings { data },
+            titles: HashMap::new(),
+            stars: HashMap::new(),
+        }
+    }
+
+    /// Read the titles and stars of the puzzles, if the table shows them.
+    fn read(year: Year, timings: &Timings, config: &ReadmeConfig) -> Self {
+        let mut section = Section::new(year, timings);
+        let columns = config.columns.as_deref().unwrap_or_default();
+
+        if columns.contains(&Column::Title) {
+            for timing in &section.timings.data {
+                let title = fs::read_to_string(get_puzzle_path(year, timing.day))
+                    .ok()
+                    .and_then(|markdown| puzzle::title(&markdown).map(String::from));
+                if let Some(title) = title {
+                    section.titles.insert(timing.day, title);
+                }
+            }
+        }
+
+        if columns.contains(&Column::Stars) {
+            let answers = Answers::read_from_file(year).unwrap_or_default();
+            for timing in &section.timings.data {
+                let stars = [1, 2]
+                    .into_iter()
+                    .filter(|part| answers.get(timing.day, *part).is_some())
+                    .count();
+                section.stars.insert(timing.day, stars);
+            }
+        }
+
+        section
+    }
+}
+
+fn construct_table(
+    prefix: &str,
+    sections: &[Section],
+    config: &ReadmeConfig,
+    chart: Option<&str>,
+) -> String {
+    let mut lines: Vec<String> = vec![MARKER.into(), format!("{prefix} Benchmarks")];
+
+    for section in sections {
+        if config.per_year {
+            lines.push(String::new());
+            lines.push(format!("{prefix}# {}", section.year));
+        }
+
+        lines.push(String::new());
+        lines.extend(construct_section(section, config));
+    }
+
+    if let Some(path) = chart {
+        lines.push(String::new());
+        lines.push(format!("![{CHART_ALT}]({path})"));
     }
 
-    lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+fn construct_section(section: &Section, config: &ReadmeConfig) -> Vec<String> {
+    let data = &section.timings.data;
+
+    // by default, only show a parse column if at least one solution has a separate parse step.
+    let columns = config.columns.clone().unwrap_or_else(|| {
+        if data.iter().any(|t| t.parse.is_some()) {
+            vec![Column::Parse]
+        } else {
+            vec![]
+        }
+    });
+
+    // columns that describe the puzzle come before the part timings, metrics after them.
+    let (before, after): (Vec<Column>, Vec<Column>) =
+        columns.iter().partition(|c| **c < Column::Memory);
+
+    let total_nanos: f64 = data.iter().map(|t| t.total_nanos).sum();
+
+    let mut header = vec!["Day"];
+    header.extend(before.iter().map(|c| column_name(*c)));
+    header.extend(["Part 1", "Part 2"]);
+    header.extend(after.iter().map(|c| column_name(*c)));
+
+    let mut lines = vec![
+        format!("| {} |", header.join(" | ")),
+        format!("| {}  |", vec![":---:"; header.len()].join(" | ")),
+    ];
+
+    for timing in data {
+        let path = format!("./{}", get_bin_path(section.year, timing.day));
+        let mut cells = vec![format!("[Day {}]({})", timing.day.into_inner(), path)];
+
+        let cell = |column: Column| match column {
+            Column::Title => section
+                .titles
+                .get(&timing.day)
+                .cloned()
+                .unwrap_or_else(|| "-".into()),
+            Column::Stars => match section.stars.get(&timing.day) {
+                Some(stars) if *stars > 0 => "⭐".repeat(*stars),
+                _ => "-".into(),
+            },
+            Column::Parse => duration(timing.parse),
+            Column::Memory => format!(
+                "`{}`",
+                timing.peak_bytes().map_or_else(|| "-".into(), format_bytes)
+            ),
+            Column::Allocations => {
+                let count = |alloc: &Option<AllocStats>| {
+                    alloc.map_or_else(|| "-".into(), |a| a.count.to_string())
+                };
+                format!(
+                    "{} / {}",
+                    count(&timing.part_1_alloc),
+                    count(&timing.part_2_alloc)
+                )
+            }
+            Column::Samples => {
+                let samples = |stats: &Option<Statistics>| {
+                    stats
+                        .as_ref()
+                        .map_or_else(|| "-".into(), |s| s.samples.to_string())
+                };
+                format!(
+                    "{} / {}",
+                    samples(&timing.part_1_stats),
+                    samples(&timing.part_2_stats)
+                )
+            }
+            Column::Share => share_bar(timing.total_nanos, total_nanos),
+        };
+
+        cells.extend(before.iter().map(|c| cell(*c)));
+        cells.push(duration(timing.part_1));
+        cells.push(duration(timing.part_2));
+        cells.extend(after.iter().map(|c| cell(*c)));
+
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {:.2}ms**", total_nanos / 1_000_000_f64));
+
+    if let Some(count) = config.slowest.filter(|count| *count > 0) {
+        let mut slowest: Vec<_> = data.iter().collect();
+        slowest.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let days: Vec<String> = slowest
+            .iter()
+            .take(usize::try_from(count).unwrap_or(usize::MAX))
+            .map(|t| {
+                format!(
+                    "Day {} ({})",
+                    t.day.into_inner(),
+                    format_nanos(t.total_nanos.round() as u64)
+                )
+            })
+            .collect();
+
+        if !days.is_empty() {
+            lines.push(String::new());
+            lines.push(format!("**Slowest:** {}", days.join(", ")));
+        }
+    }
+
+    lines
+}
+
+fn column_name(column: Column) -> &'static str {
+    match column {
+        Column::Title => "Title",
+        Column::Stars => "Stars",
+        Column::Parse => "Parse",
+        Column::Memory => "Memory",
+        Column::Allocations => "Allocations",
+        Column::Samples => "Samples",
+        Column::Share => "Share",
+    }
+}
+
+fn duration(nanos: Option<u64>) -> String {
+    format!("`{}`", nanos.map_or_else(|| "-".into(), format_nanos))
+}
+
+/// Bar of up to ten characters with eighth steps, followed by the share in percent.
+fn share_bar(nanos: f64, total_nanos: f64) -> String {
+    const PARTIAL: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+    let share = if total_nanos > 0.0 {
+        (nanos / total_nanos).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let eighths = (share * 80.0).round() as usize;
+    let bar = format!("{}{}", "█".repeat(eighths / 8), PARTIAL[eighths % 8]);
+
+    format!("{bar} {:.1}%", share * 100.0)
+}
+
+/// Replace the benchmark table, keeping an embedded chart. Returns the path of the chart.
+fn update_content(
+    s: &mut String,
+    sections: &[Section],
+    config: &ReadmeConfig,
+) -> Result<Option<String>, Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let chart = find_chart(&s[positions.pos_start..positions.pos_end]);
+    let table = construct_table("##", sections, config, chart.as_deref());
+    s.replace_range(positions.pos_start..positions.pos_end, &table);
+    Ok(chart)
+}
+
+/// Replace the benchmark table and embed the chart at `chart` below it.
+fn embed_content(
+    s: &mut String,
+    sections: &[Section],
+    config: &ReadmeConfig,
+    chart: &str,
+) -> Result<(), Error> {
+    let positions = locate_table(s)?;
+    let table = construct_table("##", sections, config, Some(chart));
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Sections of the table: the current year, and with `per_year`, every other year with stored timings.
+fn read_sections(year: Year, timings: &Timings, config: &ReadmeConfig) -> Vec<Section> {
+    if !config.per_year {
+        return vec![Section::read(year, timings, config)];
+    }
+
+    let mut years = stored_years();
+    if !years.contains(&year) {
+        years.push(year);
+        years.sort_unstable_by(|a, b| b.cmp(a));
+    }
+
+    years
+        .into_iter()
+        .filter_map(|y| {
+            if y == year {
+                Some(Section::read(y, timings, config))
+            } else {
+                // a year that can not be read is left out instead of failing the update.
+                let timings = Timings::read_from_file(y).ok()?;
+                Some(Section::read(y, &timings, config))
+            }
+        })
+        .collect()
+}
+
+/// Update the benchmark table of a year with the most recent timing of each day.
+/// If a chart is embedded in the table, it is rendered again.
+pub fn update(year: Year, timings: Timings) -> Result<(), Error> {
     let path = "README.md";
+    let config = Config::read_from_file().map_err(Error::Parser)?.readme;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let sections = read_sections(year, &timings, &config);
+    let chart = update_content(&mut readme, &sections, &config)?;
+    fs::write(path, &readme)?;
+
+    if let Some(chart) = chart {
+        let svg = export::render(year, &timings, Format::Svg).map_err(Error::Parser)?;
+        fs::write(chart, svg)?;
+    }
+
+    Ok(())
+}
+
+/// Update the benchmark table of a year and embed the chart written to `chart`, a path relative to the readme.
+pub fn embed_chart(year: Year, timings: Timings, chart: &str) -> Result<(), Error> {
+    let path = "README.md";
+    let config = Config::read_from_file().map_err(Error::Parser)?.readme;
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    let sections = read_sections(year, &timings, &config);
+    let chart = if chart.starts_with("./") || chart.starts_with("../") {
+        chart.to_string()
+    } else {
+        format!("./{chart}")
+    };
+    embed_content(&mut readme, &sections, &config, &chart)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{construct_table, embed_content, update_content, Section, MARKER};
+    use crate::template::alloc::AllocStats;
+    use crate::template::config::{Column, ReadmeConfig};
+    use crate::template::statistics::Statistics;
+    use crate::{day, template::timings::Timing, template::timings::Timings, template::Year};
+
+    fn get_mock_year() -> Year {
+        Year::new(2024).unwrap()
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    part_1: Some(10_000_000),
+                    part_2: Some(20_000_000),
+                    total_nanos: 3e+7,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    part_1: Some(30_000_000),
+                    part_2: Some(40_000_000),
+                    total_nanos: 7e+7,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    part_1: Some(40_000_000),
+                    part_2: Some(50_000_000),
+                    total_nanos: 9e+7,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
     }
 
+    fn update(s: &mut String, timings: Timings) -> Option<String> {
+        let sections = [Section::new(get_mock_year(), &timings)];
+        update_content(s, &sections, &ReadmeConfig::default()).unwrap()
+    }
+
     #[test]
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, get_mock_timings());
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, get_mock_timings());
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, get_mock_timings());
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, get_mock_timings());
+        update(&mut s, get_mock_timings());
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +452,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update(&mut s, get_mock_timings());
         let expected = [
             "foo",
             "bar",
@@ -169,9 +461,36 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2024-01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2024-02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2024-04.rs) | `40.0ms` | `50.0ms` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_parse() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some(5_000_000);
+
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        update(&mut s, timings);
+        let expected = [
+            "foo",
+            "bar",
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2024-01.rs) | `-` | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2024-02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2024-04.rs) | `-` | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +499,101 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn keeps_embedded_chart() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        let sections = [Section::new(get_mock_year(), &get_mock_timings())];
+        embed_content(
+            &mut s,
+            &sections,
+            &ReadmeConfig::default(),
+            "./data/2024/benchmarks.svg",
+        )
+        .unwrap();
+        assert!(s.contains("**Total: 190.00ms**\n\n![Benchmarks chart](./data/2024/benchmarks.svg)\n<!--- benchmarking table --->"));
+
+        let mut timings = get_mock_timings();
+        timings.data[0].total_nanos = 1e+7;
+        let chart = update(&mut s, timings);
+        assert_eq!(chart.as_deref(), Some("./data/2024/benchmarks.svg"));
+        assert!(s.contains("**Total: 170.00ms**"));
+        assert_eq!(s.matches("![Benchmarks chart]").count(), 1);
+    }
+
+    #[test]
+    fn format_benchmarks_with_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_alloc = Some(AllocStats {
+            bytes: 4096,
+            count: 12,
+            peak_bytes: 1536,
+        });
+        timings.data[1].part_1_stats = Some(Statistics {
+            min: 1.0,
+            median: 1.0,
+            mean: 1.0,
+            p95: 1.0,
+            stddev: 0.0,
+            samples: 120,
+            outliers: 0,
+        });
+
+        let mut section = Section::new(get_mock_year(), &timings);
+        section.titles.insert(day!(1), "Historian Hysteria".into());
+        section.stars.insert(day!(1), 2);
+        section.stars.insert(day!(2), 1);
+
+        let config = ReadmeConfig {
+            columns: Some(vec![
+                Column::Title,
+                Column::Stars,
+                Column::Parse,
+                Column::Memory,
+                Column::Allocations,
+                Column::Samples,
+                Column::Share,
+            ]),
+            slowest: Some(2),
+            per_year: false,
+        };
+
+        let table = construct_table("##", &[section], &config, None);
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Title | Stars | Parse | Part 1 | Part 2 | Memory | Allocations | Samples | Share |",
+            "| :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2024-01.rs) | Historian Hysteria | ⭐⭐ | `-` | `10.0ms` | `20.0ms` | `1.5 KiB` | 12 / - | - / - | █▋ 15.8% |",
+            "| [Day 2](./src/bin/2024-02.rs) | - | ⭐ | `-` | `30.0ms` | `40.0ms` | `-` | - / - | 120 / - | ███▋ 36.8% |",
+            "| [Day 4](./src/bin/2024-04.rs) | - | - | `-` | `40.0ms` | `50.0ms` | `-` | - / - | - / - | ████▊ 47.4% |",
+            "",
+            "**Total: 190.00ms**",
+            "",
+            "**Slowest:** Day 4 (90.0ms), Day 2 (70.0ms)",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(table, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_per_year() {
+        let config = ReadmeConfig {
+            per_year: true,
+            ..ReadmeConfig::default()
+        };
+        let sections = [
+            Section::new(get_mock_year(), &get_mock_timings()),
+            Section::new(Year::new(2023).unwrap(), &get_mock_timings()),
+        ];
+
+        let table = construct_table("##", &sections, &config, None);
+        assert!(table
+            .starts_with("<!--- benchmarking table --->\n## Benchmarks\n\n### 2024\n\n| Day |"));
+        assert!(table.contains("\n\n### 2023\n\n| Day |"));
+        assert!(table.contains("[Day 1](./src/bin/2023-01.rs)"));
+        assert_eq!(table.matches("**Total: 190.00ms**").count(), 2);
+    }
 }
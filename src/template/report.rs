@@ -0,0 +1,339 @@
+/// Machine-readable records emitted by the runner for every solution part.
+/// Records are written as JSON lines to the file referenced by `AOC_REPORT_FILE`, if set.
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+    time::Duration,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::alloc::{parse_alloc, AllocStats};
+use crate::template::config::BenchSettings;
+use crate::template::statistics::Statistics;
+use crate::template::Day;
+
+pub const REPORT_FILE_ENV: &str = "AOC_REPORT_FILE";
+
+/// Part of records that concern the whole day rather than a step, e.g. a day timeout after all steps finished.
+pub const WHOLE_DAY: u8 = u8::MAX;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    Unsolved,
+    /// The part did not finish within its timeout and was stopped.
+    Timeout,
+    /// The part panicked.
+    Panicked,
+    /// The part started but has not finished yet. Lets the parent tell which part was running if it stops the bin.
+    Running,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Solved => "solved",
+            Status::Unsolved => "unsolved",
+            Status::Timeout => "timeout",
+            Status::Panicked => "panicked",
+            Status::Running => "running",
+        }
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "solved" => Ok(Status::Solved),
+            "unsolved" => Ok(Status::Unsolved),
+            "timeout" => Ok(Status::Timeout),
+            "panicked" => Ok(Status::Panicked),
+            "running" => Ok(Status::Running),
+            x => Err(format!("unknown status `{x}`.")),
+        }
+    }
+}
+
+/// Outcome of running a single solution part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartReport {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub statistics: Statistics,
+    /// Settings the part was benched with, if it was benched.
+    pub bench: Option<BenchSettings>,
+    /// Allocations of the part, if the `alloc-metrics` feature is enabled.
+    pub alloc: Option<AllocStats>,
+    pub status: Status,
+}
+
+/// Append a record to the report file, if the parent process asked for one.
+pub fn emit(report: &PartReport) {
+    let Ok(path) = env::var(REPORT_FILE_ENV) else {
+        return;
+    };
+
+    if let Err(e) = append(Path::new(&path), report) {
+        eprintln!("Failed to write report to \"{path}\": {e}");
+    }
+}
+
+/// Record that a part (or the parse step, as part `0`) starts running.
+pub fn emit_running(day: Day, part: u8) {
+    emit(&PartReport {
+        day,
+        part,
+        answer: None,
+        statistics: Statistics::from_samples(&[Duration::ZERO]),
+        bench: None,
+        alloc: None,
+        status: Status::Running,
+    });
+}
+
+fn append(path: &Path, report: &PartReport) -> Result<(), io::Error> {
+    let line = JsonValue::from(report)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Read all records from a report file. A missing file yields no records.
+pub fn read_file(path: &Path) -> Result<Vec<PartReport>, String> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Ok(vec![]);
+    };
+
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(PartReport::from_str)
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert(
+            "nanos".into(),
+            JsonValue::Number(value.statistics.median.round()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "samples".into(),
+            JsonValue::Number(value.statistics.samples as f64),
+        );
+        map.insert("statistics".into(), JsonValue::from(&value.statistics));
+        map.insert(
+            "bench".into(),
+            value
+                .bench
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "alloc".into(),
+            value
+                .alloc
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl FromStr for PartReport {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("report line is not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected report to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected report.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected report.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected report.answer to be null or string.")?;
+
+        let statistics = json
+            .get("statistics")
+            .ok_or("Expected report.statistics to be present.")
+            .map(Statistics::try_from)??;
+
+        let bench = match json.get("bench") {
+            Some(v) if !v.is_null() => Some(BenchSettings::try_from(v)?),
+            _ => None,
+        };
+
+        let alloc = parse_alloc(json.get("alloc"))?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected report.status to be a string.")?
+            .parse()?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(PartReport {
+            day,
+            part: *part as u8,
+            answer: answer.cloned(),
+            statistics,
+            bench,
+            alloc,
+            status,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use tinyjson::JsonValue;
+
+    use super::{PartReport, Status};
+    use crate::day;
+    use crate::template::alloc::AllocStats;
+    use crate::template::config::BenchSettings;
+    use crate::template::statistics::Statistics;
+
+    fn statistics(median: f64, samples: u64) -> Statistics {
+        Statistics {
+            min: median,
+            median,
+            mean: median,
+            p95: median,
+            stddev: 0.0,
+            samples,
+            outliers: 0,
+        }
+    }
+
+    fn roundtrip(report: &PartReport) -> PartReport {
+        let line = JsonValue::from(report).stringify().unwrap();
+        PartReport::from_str(&line).unwrap()
+    }
+
+    #[test]
+    fn roundtrips_solved_parts() {
+        let report = PartReport {
+            day: day!(1),
+            part: 1,
+            answer: Some("42".into()),
+            statistics: statistics(74_130.0, 100_000),
+            bench: Some(BenchSettings::default()),
+            alloc: Some(AllocStats {
+                bytes: 4096,
+                count: 12,
+                peak_bytes: 1024,
+            }),
+            status: Status::Solved,
+        };
+        assert_eq!(roundtrip(&report), report);
+    }
+
+    #[test]
+    fn roundtrips_answers_with_special_characters() {
+        let report = PartReport {
+            day: day!(17),
+            part: 2,
+            answer: Some("@ @ ( ) ms (2s @ 5 samples)\n#..#".into()),
+            statistics: statistics(2e9, 5),
+            bench: None,
+            alloc: None,
+            status: Status::Solved,
+        };
+        assert_eq!(roundtrip(&report), report);
+    }
+
+    #[test]
+    fn roundtrips_unsolved_parts() {
+        let report = PartReport {
+            day: day!(25),
+            part: 2,
+            answer: None,
+            statistics: statistics(10.0, 1),
+            bench: None,
+            alloc: None,
+            status: Status::Unsolved,
+        };
+        assert_eq!(roundtrip(&report), report);
+    }
+
+    #[test]
+    fn roundtrips_timed_out_parts() {
+        let report = PartReport {
+            day: day!(17),
+            part: 2,
+            answer: None,
+            statistics: statistics(1e10, 1),
+            bench: None,
+            alloc: None,
+            status: Status::Timeout,
+        };
+        assert_eq!(roundtrip(&report), report);
+    }
+
+    #[test]
+    fn roundtrips_panicked_parts() {
+        let report = PartReport {
+            day: day!(5),
+            part: 1,
+            answer: None,
+            statistics: statistics(250.0, 1),
+            bench: None,
+            alloc: None,
+            status: Status::Panicked,
+        };
+        assert_eq!(roundtrip(&report), report);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_reports() {
+        PartReport::from_str(r#"{ "day": "01", "part": 1 }"#).unwrap();
+    }
+}
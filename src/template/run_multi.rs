@@ -1,49 +1,248 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fs, io,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::config::{BenchOverrides, Config, Timeouts};
+use crate::template::report::{self, PartReport, Status};
+use crate::template::runner::{RunOptions, Solution};
+use crate::template::{get_data_path, Day, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Options for [`run_multi`].
+pub struct RunMultiOptions<'a> {
+    pub is_release: bool,
+    pub is_timed: bool,
+    /// Bench settings, mirrored to the solutions when `is_timed` is set.
+    pub bench: BenchOverrides,
+    /// If set, solutions are run in-process instead of invoking their binaries.
+    pub registry: Option<&'a [Solution]>,
+    /// The number of days that are run concurrently.
+    pub jobs: usize,
+    /// Timeouts passed on the command-line, they override the config file.
+    pub timeouts: Timeouts,
+}
+
+impl Default for RunMultiOptions<'_> {
+    fn default() -> Self {
+        Self {
+            is_release: false,
+            is_timed: false,
+            bench: BenchOverrides::default(),
+            registry: None,
+            jobs: 1,
+            timeouts: Timeouts::default(),
+        }
+    }
+}
+
+/// Records reported by the solutions of a [`run_multi`] call.
+pub struct RunMultiResult {
+    pub reports: Vec<PartReport>,
+    /// Only set if the solutions were benched.
+    pub timings: Option<Timings>,
+}
 
-    let mut need_space = false;
+/// Run the solutions for a set of days of a year.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    options: &RunMultiOptions,
+) -> RunMultiResult {
+    let is_timed = options.is_timed;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let days: Vec<Day> = all_days(year)
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+        .collect();
+
+    let config = Config::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read config: {e}");
+        std::process::exit(1);
+    });
+
+    let mut all_reports: Vec<PartReport> = vec![];
+    let mut timings: Vec<Timing> = Vec::with_capacity(days.len());
+    let mut failures: Vec<(Day, u8, Status)> = vec![];
+
+    let mut handle_reports = |day: Day, reports: Vec<PartReport>| {
+        failures.extend(
+            reports
+                .iter()
+                .filter(|r| matches!(r.status, Status::Timeout | Status::Panicked))
+                .map(|r| (day, r.part, r.status)),
+        );
+
+        if reports.is_empty() {
+            println!("Not solved.");
+        } else {
+            let val = child_commands::timing_from_reports(&reports, day);
+            timings.push(val);
+        }
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        all_reports.extend(reports);
+    };
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    if options.jobs > 1 && is_timed {
+        eprintln!("Warning: parallel runs distort timings, running days sequentially.");
+    }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+    if options.jobs > 1 && !is_timed {
+        if options.registry.is_some() {
+            eprintln!("Note: days are run as separate binaries when running in parallel.");
+        }
+
+        run_parallel(year, &days, options, handle_reports);
+    } else {
+        for (i, day) in days.iter().enumerate() {
+            print_header(*day, i > 0);
+
+            // solutions can only be stopped when they run in their own process.
+            let has_timeouts =
+                Timeouts::resolve(&config, year, *day, &options.timeouts) != Timeouts::default();
+
+            let reports = match options.registry {
+                Some(registry) if !has_timeouts => {
+                    run_in_process(registry, year, *day, is_timed, &options.bench)
+                }
+                _ => {
+                    child_commands::run_solution(year, *day, options, false)
+                        .unwrap()
+                        .0
+                }
+            };
+
+            handle_reports(*day, reports);
+        }
+    }
+
+    if !failures.is_empty() {
+        println!("\n{ANSI_BOLD}Failed:{ANSI_RESET}");
+        for (day, part, status) in failures {
+            let status = match status {
+                Status::Timeout => "TIMEOUT",
+                _ => "PANIC",
+            };
+            match part {
+                0 => println!("Day {day} (parse): {status}"),
+                report::WHOLE_DAY => println!("Day {day}: {status}"),
+                part => println!("Day {day} (part {part}): {status}"),
             }
-        });
+        }
+    }
 
-    if is_timed {
+    let timings = is_timed.then(|| {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
+        timings
+    });
+
+    RunMultiResult {
+        reports: all_reports,
+        timings,
+    }
+}
+
+fn print_header(day: Day, need_space: bool) {
+    if need_space {
+        println!();
     }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Run solution bins for several days concurrently, using `options.jobs` workers.
+/// Output is buffered per day and printed in day order as soon as all previous days have finished.
+fn run_parallel(
+    year: Year,
+    days: &[Day],
+    options: &RunMultiOptions,
+    mut handle_reports: impl FnMut(Day, Vec<PartReport>),
+) {
+    let next_index = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|s| {
+        for _ in 0..options.jobs.min(days.len()) {
+            let tx = tx.clone();
+            let next_index = &next_index;
+
+            s.spawn(move || loop {
+                let i = next_index.fetch_add(1, Ordering::SeqCst);
+                let Some(day) = days.get(i) else {
+                    break;
+                };
+                let result = child_commands::run_solution(year, *day, options, true);
+                if tx.send((i, result)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        drop(tx);
+
+        let mut pending = BTreeMap::new();
+        let mut next_to_print = 0;
+
+        for (i, result) in rx {
+            pending.insert(i, result);
+
+            while let Some(result) = pending.remove(&next_to_print) {
+                let day = days[next_to_print];
+                print_header(day, next_to_print > 0);
+
+                let (reports, output) = result.unwrap();
+                output.iter().for_each(child_commands::Line::print);
+                handle_reports(day, reports);
+
+                next_to_print += 1;
+            }
+        }
+    });
+}
+
+/// Run the solution for a given day from the registry, if present.
+fn run_in_process(
+    registry: &[Solution],
+    year: Year,
+    day: Day,
+    is_timed: bool,
+    bench: &BenchOverrides,
+) -> Vec<PartReport> {
+    let Some(solution) = registry.iter().find(|s| s.year == year && s.day == day) else {
+        return vec![];
+    };
+
+    let input = match fs::read_to_string(get_data_path(year, &format!("inputs/{day}.txt"))) {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("could not open input file: {e}");
+            return vec![];
+        }
+    };
+
+    let options = RunOptions {
+        bench: is_timed.then(|| bench.clone()),
+        submit: None,
+        timeout: None,
+        no_wait: false,
+        examples: false,
+    };
+
+    (solution.run)(&input, &options)
 }
 
 #[allow(dead_code)]
@@ -51,6 +250,8 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    Report(String),
+    Config(String),
 }
 
 impl From<std::io::Error> for Error {
@@ -59,136 +260,257 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting the records they report.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, RunMultiOptions};
+    use crate::template::config::{Config, Timeouts};
+    use crate::template::report::{self, PartReport, Status, REPORT_FILE_ENV};
+    use crate::template::statistics::Statistics;
+    use crate::template::{get_bin_name, get_bin_path, Day, Year};
     use std::{
+        env, fs,
         io::{BufRead, BufReader},
         path::Path,
-        process::{Command, Stdio},
+        process::{self, Child, Command, Stdio},
+        sync::mpsc,
         thread,
+        time::{Duration, Instant},
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// A line of output of a solution bin.
+    pub enum Line {
+        Stdout(String),
+        Stderr(String),
+    }
+
+    impl Line {
+        pub fn print(&self) {
+            match self {
+                Line::Stdout(line) => println!("{line}"),
+                Line::Stderr(line) => eprintln!("{line}"),
+            }
+        }
+    }
+
+    /// Run the solution bin for a given day and collect the records it reports for each part.
+    /// Output is forwarded to stdout/stderr, or returned if `buffered` is set.
+    /// Parts that exceed the part timeout stop the bin themselves, if the day timeout expires the bin is killed.
+    pub fn run_solution(
+        year: Year,
+        day: Day,
+        options: &RunMultiOptions,
+        buffered: bool,
+    ) -> Result<(Vec<PartReport>, Vec<Line>), Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&get_bin_path(year, day)).exists() {
+            return Ok((vec![], vec![]));
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let config = Config::read_from_file().map_err(Error::Config)?;
+        let timeouts = Timeouts::resolve(&config, year, day, &options.timeouts);
+
+        let mut args: Vec<String> = vec!["--quiet".into(), "--bin".into(), get_bin_name(year, day)];
+
+        if options.is_release {
+            args.push("--release".into());
+        }
+
+        if cfg!(feature = "alloc-metrics") {
+            // count the allocations of the solution bins as well.
+            args.extend(["--features".into(), "alloc-metrics".into()]);
+        }
+
+        if timeouts.day.is_some() {
+            // build upfront, so compilation does not count towards the day timeout.
+            Command::new("cargo")
+                .arg("build")
+                .args(&args)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .status()?;
+        }
+
+        args.insert(0, "run".into());
+
+        let mut bin_args: Vec<String> = vec![];
 
-        if is_release {
-            args.push("--release");
+        if options.is_timed {
+            // mirror `--time` flag and bench settings to child invocations.
+            bin_args.push("--time".into());
+            bin_args.extend(options.bench.to_args());
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        bin_args.extend(options.timeouts.to_args());
+
+        if !bin_args.is_empty() {
+            args.push("--".into());
+            args.extend(bin_args);
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // the child appends one record per part to this file.
+        let report_path = env::temp_dir().join(format!(
+            "advent_of_code-{}-{year}-{day}.jsonl",
+            process::id()
+        ));
+        let _ = fs::remove_file(&report_path);
+
+        // spawn child command with piped stdout/stderr and forward or buffer its output.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
+            .env(REPORT_FILE_ENV, &report_path)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
 
+        let deadline = timeouts.day.map(|timeout| Instant::now() + timeout);
+
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let (tx, rx) = mpsc::channel();
+        let stdout_tx = tx.clone();
+        let stderr_tx = tx.clone();
+
+        let handle_line = move |line: Line, tx: &mpsc::Sender<Line>| {
+            if buffered {
+                let _ = tx.send(line);
+            } else {
+                line.print();
+            }
+        };
 
-        let thread = thread::spawn(move || {
+        let stdout_thread = thread::spawn(move || {
+            stdout.lines().for_each(|line| {
+                handle_line(Line::Stdout(line.unwrap()), &stdout_tx);
+            });
+        });
+
+        let stderr_thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+                handle_line(Line::Stderr(line.unwrap()), &stderr_tx);
             });
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let is_killed = wait_until(&mut cmd, deadline)?;
+
+        stdout_thread.join().unwrap();
+        stderr_thread.join().unwrap();
+
+        let reports = report::read_file(&report_path).map_err(Error::Report);
+        let _ = fs::remove_file(&report_path);
+        let timeout = is_killed.then(|| timeouts.day.unwrap_or_default());
+        let reports = resolve_running(reports?, day, timeout);
+
+        if let Some(timeout) = timeout {
+            let message = format!("TIMEOUT: day did not finish within {timeout:.1?}, stopped.");
+            handle_line(Line::Stdout(message), &tx);
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        drop(tx);
+        let output = rx.into_iter().collect();
+
+        Ok((reports, output))
+    }
+
+    /// Drop the records of parts that started running. If the bin was stopped after `timeout`,
+    /// the part that was still running (or the whole day, if none was) is reported as timed out.
+    pub fn resolve_running(
+        reports: Vec<PartReport>,
+        day: Day,
+        timeout: Option<Duration>,
+    ) -> Vec<PartReport> {
+        let running = reports
+            .iter()
+            .filter(|r| r.status == Status::Running)
+            .map(|r| r.part)
+            .rfind(|part| {
+                !reports
+                    .iter()
+                    .any(|r| r.part == *part && r.status != Status::Running)
+            });
+
+        let mut reports: Vec<PartReport> = reports
+            .into_iter()
+            .filter(|r| r.status != Status::Running)
+            .collect();
+
+        if let Some(timeout) = timeout {
+            reports.push(PartReport {
+                day,
+                part: running.unwrap_or(report::WHOLE_DAY),
+                answer: None,
+                statistics: Statistics::from_samples(&[timeout]),
+                bench: None,
+                alloc: None,
+                status: Status::Timeout,
+            });
+        }
 
-        Ok(output)
+        reports
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+    /// Wait for a child to exit. If it still runs at the deadline, it is killed and `true` is returned.
+    pub fn wait_until(cmd: &mut Child, deadline: Option<Instant>) -> Result<bool, Error> {
+        let Some(deadline) = deadline else {
+            cmd.wait()?;
+            return Ok(false);
         };
 
-        output
+        loop {
+            if cmd.try_wait()?.is_some() {
+                return Ok(false);
+            }
+
+            if Instant::now() >= deadline {
+                cmd.kill()?;
+                cmd.wait()?;
+                return Ok(true);
+            }
+
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    /// Convert the records reported by a solution bin into a [`super::Timing`].
+    pub fn timing_from_reports(reports: &[PartReport], day: Day) -> super::Timing {
+        let mut timings = super::Timing::new(day);
+
+        reports
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
+            .filter(|r| r.day == day && r.status == Status::Solved)
+            .for_each(|r| {
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let nanos = r.statistics.median.round() as u64;
+                let stats = Some(r.statistics.clone());
+
+                if r.bench.is_some() {
+                    timings.bench = r.bench;
                 }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                match r.part {
+                    0 => {
+                        (timings.parse, timings.parse_stats, timings.parse_alloc) =
+                            (Some(nanos), stats, r.alloc);
+                    }
+                    1 => {
+                        (timings.part_1, timings.part_1_stats, timings.part_1_alloc) =
+                            (Some(nanos), stats, r.alloc);
+                    }
+                    2 => {
+                        (timings.part_2, timings.part_2_stats, timings.part_2_alloc) =
+                            (Some(nanos), stats, r.alloc);
+                    }
+                    _ => return,
                 }
 
-                timings.total_nanos += nanos;
+                timings.total_nanos += r.statistics.median;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -205,47 +527,113 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use std::time::Duration;
+
+        use super::{resolve_running, timing_from_reports};
 
         use crate::day;
+        use crate::template::report::{PartReport, Status, WHOLE_DAY};
+        use crate::template::statistics::Statistics;
+
+        fn report(part: u8, median: f64, status: Status) -> PartReport {
+            PartReport {
+                day: day!(1),
+                part,
+                answer: Some("@ @ @ ( ) ms (2s @ 5 samples)".into()),
+                statistics: Statistics {
+                    min: median / 2.0,
+                    median,
+                    mean: median * 2.0,
+                    p95: median * 3.0,
+                    stddev: 1.0,
+                    samples: 5,
+                    outliers: 1,
+                },
+                bench: None,
+                alloc: None,
+                status,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn converts_reports_to_timings() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    report(1, 74.0, Status::Solved),
+                    report(2, 74_130_000.0, Status::Solved),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap(), 74);
+            assert_eq!(res.part_2.unwrap(), 74_130_000);
+            assert_eq!(res.part_1_stats.unwrap().p95, 222.0);
+            assert_eq!(res.parse.is_none(), true);
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn converts_parse_reports() {
+            let mut parse = report(0, 1_000.0, Status::Solved);
+            parse.answer = None;
+
+            let res = timing_from_reports(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    parse,
+                    report(1, 74.0, Status::Solved),
+                    report(2, 26.0, Status::Solved),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 1100_f64);
+            assert_eq!(res.parse.unwrap(), 1_000);
+            assert_eq!(res.parse_stats.unwrap().median, 1_000.0);
+        }
+
+        #[test]
+        fn attributes_timeouts_to_running_parts() {
+            let timeout = Some(Duration::from_secs(1));
+            let parts = |reports: &[PartReport]| -> Vec<(u8, Status)> {
+                reports.iter().map(|r| (r.part, r.status)).collect()
+            };
+
+            // hangs while parsing.
+            let reports = resolve_running(vec![report(0, 0.0, Status::Running)], day!(1), timeout);
+            assert_eq!(parts(&reports), [(0, Status::Timeout)]);
+
+            // hangs in part 2.
+            let reports = resolve_running(
+                vec![
+                    report(1, 0.0, Status::Running),
+                    report(1, 74.0, Status::Solved),
+                    report(2, 0.0, Status::Running),
                 ],
                 day!(1),
+                timeout,
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(parts(&reports), [(1, Status::Solved), (2, Status::Timeout)]);
+
+            // all parts finished, the day still timed out.
+            let finished = vec![
+                report(1, 0.0, Status::Running),
+                report(1, 74.0, Status::Solved),
+                report(2, 0.0, Status::Running),
+                report(2, 26.0, Status::Solved),
+            ];
+            let reports = resolve_running(finished.clone(), day!(1), timeout);
+            assert_eq!(reports.last().unwrap().part, WHOLE_DAY);
+            assert_eq!(reports.last().unwrap().status, Status::Timeout);
+
+            // without a timeout, only the records of finished parts are kept.
+            let reports = resolve_running(finished, day!(1), None);
+            assert_eq!(parts(&reports), [(1, Status::Solved), (2, Status::Solved)]);
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
+        fn converts_missing_parts() {
+            let res = timing_from_reports(
                 &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
+                    report(1, 10.0, Status::Unsolved),
+                    report(2, 10.0, Status::Unsolved),
                 ],
                 day!(1),
             );
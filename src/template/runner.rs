@@ -1,65 +1,283 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, RecvTimeoutError};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, fs, process, thread};
 
+use crate::template::alloc::{self, AllocStats};
+use crate::template::aoc_client::{self, SubmitOutcome};
+use crate::template::commands::{examples, scaffold};
+use crate::template::config::{BenchOverrides, BenchSettings, Config, Timeouts};
+use crate::template::puzzle;
+use crate::template::rate_limit::{self, Clock, SystemClock};
+use crate::template::report::{self, PartReport, Status};
+use crate::template::statistics::Statistics;
+use crate::template::submissions::{self, Submission, Submissions};
+use crate::template::timings::format_bytes;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{get_data_path, Day, Year, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Exit status of a solution bin that was stopped because a part exceeded its timeout.
+pub const TIMEOUT_EXIT_CODE: i32 = 124;
+
+/// Exit status of a solution bin if any of its parts panicked.
+pub const PANIC_EXIT_CODE: i32 = 101;
+
+/// Options that solutions are run with.
+/// Solution bins read them from the command-line, the in-process registry passes them explicitly.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Set if the solution should be benched (`--time`), holds the bench settings passed on the command-line.
+    pub bench: Option<BenchOverrides>,
+    /// The part that should be submitted (`--submit <part>`).
+    pub submit: Option<u8>,
+    /// Overrides the configured part timeout (`--timeout <ms>`).
+    pub timeout: Option<Duration>,
+    /// Fail instead of waiting if a submission is rate-limited (`--no-wait`).
+    pub no_wait: bool,
+    /// Extract the example of part two once it is unlocked (`--examples`).
+    pub examples: bool,
+}
+
+impl RunOptions {
+    /// Parse the arguments passed to a solution bin.
+    pub fn from_args() -> Self {
+        let mut args = pico_args::Arguments::from_env();
+
+        let parse = |args: &mut pico_args::Arguments| -> Result<Self, pico_args::Error> {
+            let bench = if args.contains("--time") {
+                Some(BenchOverrides::parse(args)?)
+            } else {
+                None
+            };
+
+            Ok(Self {
+                bench,
+                submit: args.opt_value_from_str("--submit")?,
+                timeout: args
+                    .opt_value_from_str("--timeout")?
+                    .map(Duration::from_millis),
+                no_wait: args.contains("--no-wait"),
+                examples: args.contains("--examples"),
+            })
+        };
+
+        parse(&mut args).unwrap_or_else(|e| {
+            eprintln!("Unexpected command-line input: {e}. Format: cargo solve 1 --submit 1");
+            process::exit(1);
+        })
+    }
+}
+
+/// A solution that can be run in-process, see the `registry` feature.
+pub struct Solution {
+    pub year: Year,
+    pub day: Day,
+    pub run: fn(&str, &RunOptions) -> Vec<PartReport>,
+}
+
+/// Run the parse step of a solution. It is timed like a part and reported as part `0`.
+/// Returns `None` if the parse step panicked.
+pub fn run_parse<P>(
+    func: impl Fn(&str) -> P,
+    input: &str,
+    year: Year,
+    day: Day,
+    options: &RunOptions,
+) -> (Option<P>, PartReport) {
+    let bench = get_bench_settings(year, day, options);
+    report::emit_running(day, 0);
+    let watchdog = start_watchdog(year, day, 0, "Parse", options);
+
+    let timer = Instant::now();
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_timed(func, input, bench.as_ref(), |_| {
+            print!("Parse: ✔");
+        })
+    }));
+
+    drop(watchdog);
+
+    let (parsed, statistics, alloc, status) = match outcome {
+        Ok((parsed, statistics, alloc)) => {
+            println!(
+                "\rParse: ✔{}{}",
+                format_statistics(&statistics),
+                format_alloc(alloc.as_ref())
+            );
+            (Some(parsed), statistics, alloc, Status::Solved)
+        }
+        Err(payload) => {
+            println!("Parse: PANIC ({})", panic_message(payload.as_ref()));
+            let statistics = Statistics::from_samples(&[timer.elapsed()]);
+            (None, statistics, None, Status::Panicked)
+        }
+    };
+
+    let report = PartReport {
+        day,
+        part: 0,
+        answer: None,
+        statistics,
+        bench,
+        alloc,
+        status,
+    };
+
+    report::emit(&report);
+
+    (parsed, report)
+}
+
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    year: Year,
+    day: Day,
+    part: u8,
+    options: &RunOptions,
+) -> PartReport {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let bench = get_bench_settings(year, day, options);
+    report::emit_running(day, part);
+    let watchdog = start_watchdog(year, day, part, &part_str, options);
+
+    let timer = Instant::now();
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        run_timed(func, input, bench.as_ref(), |result| {
+            print_result(result, &part_str, "");
+        })
+    }));
+
+    drop(watchdog);
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let (result, statistics, alloc, status) = match outcome {
+        Ok((result, statistics, alloc)) => {
+            let details = format!(
+                "{}{}",
+                format_statistics(&statistics),
+                format_alloc(alloc.as_ref())
+            );
+            print_result(&result, &part_str, &details);
+            let status = if result.is_some() {
+                Status::Solved
+            } else {
+                Status::Unsolved
+            };
+            (result, statistics, alloc, status)
+        }
+        Err(payload) => {
+            println!("{part_str}: PANIC ({})", panic_message(payload.as_ref()));
+            let statistics = Statistics::from_samples(&[timer.elapsed()]);
+            (None, statistics, None, Status::Panicked)
+        }
+    };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    let report = PartReport {
+        day,
+        part,
+        answer: result.as_ref().map(ToString::to_string),
+        statistics,
+        bench,
+        alloc,
+        status,
+    };
+
+    report::emit(&report);
+
+    report
+}
+
+/// Exit a solution bin with [`PANIC_EXIT_CODE`] if any of its parts panicked.
+pub fn exit_with_status(reports: &[PartReport]) {
+    if reports.iter().any(|r| r.status == Status::Panicked) {
+        process::exit(PANIC_EXIT_CODE);
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Extract the message of a caught panic. Panics raised via `panic!` or `unwrap` carry a `&str` or `String`.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".to_string()
+    }
+}
+
+/// Run a solution part. The behavior differs depending on whether bench settings are passed:
+///  1. without settings, the function is executed once.
+///  2. with settings, the function is warmed up and then benched (by default approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Allocations are counted during the first execution, if the `alloc-metrics` feature is enabled.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    bench_settings: Option<&BenchSettings>,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Statistics, Option<AllocStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, alloc) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        alloc::measure(|| func(input))
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
+    let statistics = match bench_settings {
+        Some(settings) => bench(func, input, &base_time, settings),
+        None => Statistics::from_samples(&[base_time]),
     };
 
-    (result, run.0, run.1)
+    (result, statistics, alloc)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    settings: &BenchSettings,
+) -> Statistics {
     let mut stdout = stdout();
 
+    let mut estimate = *base_time;
+
+    if !settings.warm_up.is_zero() {
+        print!(" > {ANSI_ITALIC}warming up{ANSI_RESET}");
+        let _ = stdout.flush();
+
+        let timer = Instant::now();
+        let mut runs = 0;
+
+        while timer.elapsed() < settings.warm_up {
+            black_box(func(black_box(input.clone())));
+            runs += 1;
+        }
+
+        // the warm-up may end before a single run if it is shorter than the timer resolution.
+        estimate = timer.elapsed() / runs.max(1);
+    }
+
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
+    #[allow(clippy::cast_possible_truncation)]
     let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+        (settings.measurement_time.as_nanos() / cmp::max(estimate.as_nanos(), 10)).clamp(
+            u128::from(settings.min_samples),
+            u128::from(settings.max_samples),
+        ) as u64;
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -71,27 +289,108 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    Statistics::from_samples(&timers)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Starts a thread that stops the process if a part runs longer than its timeout.
+/// The part is reported as timed out before exiting. Drop the returned handle once the part finished.
+fn start_watchdog(
+    year: Year,
+    day: Day,
+    part: u8,
+    label: &str,
+    options: &RunOptions,
+) -> Option<mpsc::Sender<()>> {
+    let args = Timeouts {
+        part: options.timeout,
+        day: None,
+    };
+    let timeout = Timeouts::resolve(&read_config(), year, day, &args).part?;
+
+    let (tx, rx) = mpsc::channel::<()>();
+    let label = label.to_string();
+
+    thread::spawn(move || {
+        if rx.recv_timeout(timeout) == Err(RecvTimeoutError::Timeout) {
+            println!("\r{label}: TIMEOUT ({timeout:.1?})");
+
+            report::emit(&PartReport {
+                day,
+                part,
+                answer: None,
+                statistics: Statistics::from_samples(&[timeout]),
+                bench: None,
+                alloc: None,
+                status: Status::Timeout,
+            });
+
+            process::exit(TIMEOUT_EXIT_CODE);
+        }
+    });
+
+    Some(tx)
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+/// Resolve bench settings for a day from the config file and the run options.
+/// Returns `None` if the solution should not be benched.
+fn get_bench_settings(year: Year, day: Day, options: &RunOptions) -> Option<BenchSettings> {
+    let overrides = options.bench.as_ref()?;
+    Some(BenchSettings::resolve(&read_config(), year, day, overrides))
+}
+
+fn read_config() -> Config {
+    Config::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read config: {e}");
+        process::exit(1);
+    })
+}
+
+fn format_statistics(statistics: &Statistics) -> String {
+    let median = statistics.median_duration();
+
+    if statistics.samples == 1 {
+        return format!(" ({median:.1?})");
+    }
+
+    let as_duration = |nanos: f64| {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(nanos.round() as u64)
+    };
+
+    let mut str = format!(
+        " ({median:.1?} @ {} samples; min {:.1?}, mean {:.1?}, p95 {:.1?}, σ {:.1?}",
+        statistics.samples,
+        as_duration(statistics.min),
+        as_duration(statistics.mean),
+        as_duration(statistics.p95),
+        as_duration(statistics.stddev),
+    );
+
+    if statistics.outliers > 0 {
+        str.push_str(&format!("; {} outliers", statistics.outliers));
     }
+
+    str.push(')');
+    str
+}
+
+fn format_alloc(alloc: Option<&AllocStats>) -> String {
+    let Some(alloc) = alloc else {
+        return String::new();
+    };
+
+    let allocations = if alloc.count == 1 {
+        "allocation"
+    } else {
+        "allocations"
+    };
+
+    format!(
+        " [{} in {} {allocations}, peak {}]",
+        format_bytes(alloc.bytes),
+        alloc.count,
+        format_bytes(alloc.peak_bytes)
+    )
 }
 
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
@@ -129,41 +428,123 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+/// Submit the answer of the part passed with `--submit <part>`, if any.
+/// Answers that previous submissions show to be wrong are refused. `example` computes the answer of a part for its example input.
+pub fn submit(
+    reports: &[PartReport],
+    year: Year,
     day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+    options: &RunOptions,
+    example: impl Fn(u8) -> Option<String>,
+) {
+    let Some(part) = options.submit else {
+        return;
+    };
 
-    if !args.contains(&"--submit".into()) {
-        return None;
-    }
+    let Some(answer) = reports
+        .iter()
+        .find(|r| r.part == part)
+        .and_then(|r| r.answer.as_deref())
+    else {
+        eprintln!("Part {part} has no answer to submit.");
+        process::exit(1);
+    };
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+    let submissions = Submissions::read_from_file(year).unwrap_or_else(|e| {
+        eprintln!("Failed to read submissions: {e}");
+        process::exit(1);
+    });
+
+    if let Err(reason) = submissions.check(day, part, answer) {
+        eprintln!("Refusing to submit: {reason}");
         process::exit(1);
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    if answer.trim() == "0" {
+        println!("Warning: the answer is `0`.");
+    }
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+    if example(part).as_deref().map(str::trim) == Some(answer.trim()) {
+        println!("Warning: the answer equals the answer for the example input.");
+    }
+
+    let mut clock = SystemClock;
+
+    if let Some(cooldown) = submissions.cooldown(day, clock.now()) {
+        if options.no_wait {
+            eprintln!(
+                "You gave an answer too recently, wait {}s before submitting again.",
+                cooldown.as_secs()
+            );
+            process::exit(1);
+        }
+        rate_limit::countdown(&mut clock, cooldown);
+    }
+
+    let outcome = rate_limit::submit_with_retry(
+        &mut clock,
+        options.no_wait,
+        || {
+            println!("Submitting result...");
+            aoc_client::submit(year, day, part, answer)
+        },
+        |outcome| {
+            let submission = Submission::new(day, part, answer, outcome.clone());
+            if let Err(e) = submissions::record(year, submission) {
+                eprintln!("Failed to record submission: {e}");
+            }
+        },
+    )
+    .unwrap_or_else(|e| {
+        eprintln!("Failed to submit: {e}");
         process::exit(1);
+    });
+
+    match outcome {
+        SubmitOutcome::RateLimited(_) => process::exit(1),
+        SubmitOutcome::Correct if part == 1 => unlock_part_two(year, day, options),
+        _ => {}
+    }
+}
+
+/// Download the puzzle description again after part one was solved and print the newly unlocked part two.
+fn unlock_part_two(year: Year, day: Day, options: &RunOptions) {
+    let markdown = match aoc_client::refresh_puzzle(year, day) {
+        Ok(markdown) => markdown,
+        Err(e) => {
+            eprintln!("Failed to download part two: {e}");
+            return;
+        }
     };
 
-    if part_submit != part {
-        return None;
+    if let Some(part_two) = puzzle::part_two(&markdown) {
+        println!("\n{part_two}");
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    if options.examples {
+        examples::handle_part_two(year, day);
     }
+    scaffold::update_tests(year, day);
+}
+
+/// Read the example input of a part, `<day>-<part>.txt` if present, `<day>.txt` otherwise.
+pub fn read_example(year: Year, day: Day, part: u8) -> Option<String> {
+    [
+        format!("examples/{day}-{part}.txt"),
+        format!("examples/{day}.txt"),
+    ]
+    .iter()
+    .find_map(|path| fs::read_to_string(get_data_path(year, path)).ok())
+    .filter(|example| !example.trim().is_empty())
+}
+
+/// Compute the answer of a part for its example input. Returns `None` if the part panicked.
+pub fn example_answer<T: Display>(func: impl FnOnce() -> Option<T>) -> Option<String> {
+    // the solution may not handle the example, its panic message would only be noise here.
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    panic::set_hook(hook);
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    result.ok().flatten().map(|answer| answer.to_string())
 }
@@ -0,0 +1,198 @@
+/// Summary statistics over the samples collected while benching a solution part.
+use std::{collections::HashMap, time::Duration};
+
+use tinyjson::JsonValue;
+
+/// Samples further than this many interquartile ranges outside of the quartiles are considered outliers ("far out").
+const IQR_FACTOR: f64 = 3.0;
+
+/// Describes the distribution of bench samples. All durations are in nanoseconds.
+/// All samples are used for `min`, `median`, `mean`, `p95` and `stddev`, so that e.g. slow runs caused by cache
+/// effects are not hidden. `outliers` only counts the samples that lie far outside of the quartiles.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Statistics {
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub p95: f64,
+    pub stddev: f64,
+    pub samples: u64,
+    pub outliers: u64,
+}
+
+impl Statistics {
+    /// Calculate statistics for a set of samples, counting outliers with the IQR rule.
+    ///
+    /// # Panics
+    /// Panics if `samples` is empty.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        assert!(!samples.is_empty(), "expected at least one sample.");
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let q1 = percentile(&sorted, 0.25);
+        let q3 = percentile(&sorted, 0.75);
+        let iqr = q3 - q1;
+        let (low, high) = (q1 - IQR_FACTOR * iqr, q3 + IQR_FACTOR * iqr);
+
+        let outliers = sorted.iter().filter(|x| **x < low || **x > high).count();
+
+        #[allow(clippy::cast_precision_loss)]
+        let len = sorted.len() as f64;
+        let mean = sorted.iter().sum::<f64>() / len;
+        let variance = sorted.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / len;
+
+        Statistics {
+            min: sorted[0],
+            median: percentile(&sorted, 0.5),
+            mean,
+            p95: percentile(&sorted, 0.95),
+            stddev: variance.sqrt(),
+            samples: samples.len() as u64,
+            outliers: outliers as u64,
+        }
+    }
+
+    /// The median, which is the figure reported for a part by default.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn median_duration(&self) -> Duration {
+        Duration::from_nanos(self.median.round() as u64)
+    }
+}
+
+/// Linearly interpolated percentile `p` (0.0 to 1.0) of a sorted, non-empty slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = p * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    let weight = rank - rank.floor();
+    sorted[lower] + (sorted[upper] - sorted[lower]) * weight
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Statistics> for JsonValue {
+    fn from(value: &Statistics) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        map.insert("stddev".into(), JsonValue::Number(value.stddev));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Statistics {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected statistics to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected statistics.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Statistics {
+            min: number("min")?,
+            median: number("median")?,
+            mean: number("mean")?,
+            p95: number("p95")?,
+            stddev: number("stddev")?,
+            samples: number("samples")? as u64,
+            outliers: number("outliers")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::Statistics;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn handles_single_samples() {
+        let stats = Statistics::from_samples(&nanos(&[100]));
+        assert_eq!(stats.min, 100.0);
+        assert_eq!(stats.median, 100.0);
+        assert_eq!(stats.mean, 100.0);
+        assert_eq!(stats.p95, 100.0);
+        assert_eq!(stats.stddev, 0.0);
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn calculates_statistics() {
+        let stats = Statistics::from_samples(&nanos(&[40, 10, 30, 20]));
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.median, 25.0);
+        assert_eq!(stats.mean, 25.0);
+        assert_eq!(stats.p95, 38.5);
+        assert_eq!(stats.stddev, 125_f64.sqrt());
+        assert_eq!(stats.samples, 4);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn counts_outliers() {
+        let stats = Statistics::from_samples(&nanos(&[10, 11, 10, 12, 11, 10, 5000]));
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.median, 11.0);
+        assert_eq!(stats.samples, 7);
+        assert_eq!(stats.outliers, 1);
+        // the outlier still counts towards the mean and the tail.
+        assert_eq!(stats.mean, 5064.0 / 7.0);
+        assert!(stats.p95 > 3000.0);
+    }
+
+    #[test]
+    fn keeps_slow_runs_of_skewed_distributions() {
+        // most runs are fast with a long tail of slower runs, a few runs are interrupted.
+        let samples: Vec<u64> = (0..200_u64)
+            .map(|i| {
+                if i % 50 == 0 {
+                    5000
+                } else {
+                    100 + i.pow(3) / 40_000
+                }
+            })
+            .collect();
+        let stats = Statistics::from_samples(&nanos(&samples));
+
+        #[allow(clippy::cast_precision_loss)]
+        let mean = samples.iter().sum::<u64>() as f64 / samples.len() as f64;
+        assert_eq!(stats.mean, mean);
+        assert_eq!(stats.samples, 200);
+        // the tail is kept, only the interrupted runs are far out.
+        assert!(stats.p95 > 250.0);
+        assert_eq!(stats.outliers, 4);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_empty_samples() {
+        Statistics::from_samples(&[]);
+    }
+}
@@ -0,0 +1,347 @@
+/// History of submitted answers, stored in `data/<year>/submissions.json`.
+/// It is used to refuse answers locally that the website would reject, which avoids submission lockouts.
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{aoc_client::SubmitOutcome, get_data_path, Day, Year};
+
+#[must_use]
+pub fn get_submissions_path(year: Year) -> String {
+    get_data_path(year, "submissions.json")
+}
+
+/// A submitted answer. `timestamp` is in seconds since the unix epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub timestamp: u64,
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: SubmitOutcome,
+}
+
+impl Submission {
+    /// A submission made now.
+    pub fn new(day: Day, part: u8, answer: &str, outcome: SubmitOutcome) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            day,
+            part,
+            answer: answer.trim().to_string(),
+            outcome,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Rehydrate submissions from the JSON file. If not present, returns an empty history.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        match fs::read_to_string(get_submissions_path(year)) {
+            Ok(s) => Submissions::try_from(s),
+            Err(_) => Ok(Submissions::default()),
+        }
+    }
+
+    /// Dehydrate submissions to the JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        fs::create_dir_all(get_data_path(year, ""))?;
+        let mut file = fs::File::create(get_submissions_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Check an answer against previous submissions of a part.
+    /// Returns the reason if the answer is known to be wrong.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), String> {
+        let answer = answer.trim();
+        let submissions = self.data.iter().filter(|s| s.day == day && s.part == part);
+
+        let mut too_high: Option<i128> = None;
+        let mut too_low: Option<i128> = None;
+
+        for submission in submissions {
+            let is_rejected = matches!(
+                submission.outcome,
+                SubmitOutcome::Incorrect | SubmitOutcome::TooHigh | SubmitOutcome::TooLow
+            );
+
+            if is_rejected && submission.answer == answer {
+                return Err(format!("`{answer}` was already submitted and rejected."));
+            }
+
+            let Ok(value) = submission.answer.parse::<i128>() else {
+                continue;
+            };
+
+            match submission.outcome {
+                SubmitOutcome::TooHigh => {
+                    too_high = Some(too_high.map_or(value, |bound| bound.min(value)));
+                }
+                SubmitOutcome::TooLow => {
+                    too_low = Some(too_low.map_or(value, |bound| bound.max(value)));
+                }
+                _ => {}
+            }
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        if let Some(bound) = too_high.filter(|bound| value >= *bound) {
+            return Err(format!(
+                "`{answer}` is too high, `{bound}` was already too high."
+            ));
+        }
+
+        if let Some(bound) = too_low.filter(|bound| value <= *bound) {
+            return Err(format!(
+                "`{answer}` is too low, `{bound}` was already too low."
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// The remaining cooldown of a day at `now` (seconds since the unix epoch), if its last submission was rate-limited.
+    pub fn cooldown(&self, day: Day, now: u64) -> Option<Duration> {
+        let last = self.data.iter().rfind(|s| s.day == day)?;
+
+        match last.outcome {
+            SubmitOutcome::RateLimited(wait) => {
+                let until = last.timestamp + wait.as_secs();
+                (until > now).then(|| Duration::from_secs(until - now))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Record a submission in the submissions file.
+pub fn record(year: Year, submission: Submission) -> Result<(), String> {
+    let mut submissions = Submissions::read_from_file(year)?;
+    submissions.data.push(submission);
+    submissions.store_file(year).map_err(|e| e.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn outcome_to_str(outcome: &SubmitOutcome) -> &'static str {
+    match outcome {
+        SubmitOutcome::Correct => "correct",
+        SubmitOutcome::Incorrect => "incorrect",
+        SubmitOutcome::TooHigh => "too_high",
+        SubmitOutcome::TooLow => "too_low",
+        SubmitOutcome::RateLimited(_) => "rate_limited",
+        SubmitOutcome::AlreadySolved => "already_solved",
+    }
+}
+
+impl From<&Submissions> for JsonValue {
+    fn from(value: &Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("submissions are not a valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected submissions to be an object.")?
+            .get("data")
+            .ok_or("expected submissions to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `submissions.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(outcome_to_str(&value.outcome).into()),
+        );
+
+        if let SubmitOutcome::RateLimited(wait) = value.outcome {
+            #[allow(clippy::cast_precision_loss)]
+            map.insert("wait_s".into(), JsonValue::Number(wait.as_secs() as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected submission to be an object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(JsonValue::get::<f64>)
+                .copied()
+                .ok_or(format!("expected submission to have a numeric `{key}`."))
+        };
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(JsonValue::get::<String>)
+                .ok_or(format!("expected submission to have a string `{key}`."))
+        };
+
+        let outcome = match string("outcome")?.as_str() {
+            "correct" => SubmitOutcome::Correct,
+            "incorrect" => SubmitOutcome::Incorrect,
+            "too_high" => SubmitOutcome::TooHigh,
+            "too_low" => SubmitOutcome::TooLow,
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            "rate_limited" => SubmitOutcome::RateLimited(Duration::from_secs(
+                number("wait_s").unwrap_or(0.0) as u64,
+            )),
+            "already_solved" => SubmitOutcome::AlreadySolved,
+            x => return Err(format!("unknown submission outcome `{x}`.")),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Submission {
+            timestamp: number("timestamp")? as u64,
+            day: Day::from_str(string("day")?).map_err(|e| e.to_string())?,
+            part: number("part")? as u8,
+            answer: string("answer")?.clone(),
+            outcome,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{Submission, Submissions};
+    use crate::day;
+    use crate::template::aoc_client::SubmitOutcome;
+
+    fn get_mock_submissions() -> Submissions {
+        Submissions {
+            data: vec![
+                Submission::new(day!(1), 1, "100", SubmitOutcome::TooHigh),
+                Submission::new(day!(1), 1, "20", SubmitOutcome::TooLow),
+                Submission::new(day!(1), 1, "80", SubmitOutcome::TooHigh),
+                Submission::new(day!(1), 1, "50", SubmitOutcome::Incorrect),
+                Submission::new(day!(1), 2, "abc", SubmitOutcome::Incorrect),
+                Submission::new(
+                    day!(1),
+                    2,
+                    "xyz",
+                    SubmitOutcome::RateLimited(Duration::from_secs(30)),
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let submissions = get_mock_submissions();
+        assert!(submissions.check(day!(1), 1, "50").is_err());
+        assert!(submissions.check(day!(1), 2, " abc\n").is_err());
+        // rate-limited answers were not checked by the website.
+        assert!(submissions.check(day!(1), 2, "xyz").is_ok());
+        // answers of other days and parts are not considered.
+        assert!(submissions.check(day!(2), 1, "50").is_ok());
+        assert!(submissions.check(day!(1), 2, "50").is_ok());
+    }
+
+    #[test]
+    fn refuses_answers_out_of_bounds() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(1), 1, "80"),
+            Err("`80` was already submitted and rejected.".into())
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "90"),
+            Err("`90` is too high, `80` was already too high.".into())
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "-5"),
+            Err("`-5` is too low, `20` was already too low.".into())
+        );
+        assert!(submissions.check(day!(1), 1, "79").is_ok());
+        assert!(submissions.check(day!(1), 1, "21").is_ok());
+        assert!(submissions.check(day!(1), 1, "not a number").is_ok());
+    }
+
+    #[test]
+    fn computes_cooldown_from_rate_limits() {
+        let mut submissions = get_mock_submissions();
+        let timestamp = submissions.data[5].timestamp;
+
+        assert_eq!(
+            submissions.cooldown(day!(1), timestamp + 10),
+            Some(Duration::from_secs(20))
+        );
+        assert_eq!(submissions.cooldown(day!(1), timestamp + 30), None);
+        assert_eq!(submissions.cooldown(day!(2), timestamp), None);
+
+        submissions
+            .data
+            .push(Submission::new(day!(1), 2, "abc", SubmitOutcome::Correct));
+        assert_eq!(submissions.cooldown(day!(1), timestamp + 10), None);
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let submissions = get_mock_submissions();
+        let json = JsonValue::from(&submissions).stringify().unwrap();
+        assert_eq!(Submissions::try_from(json).unwrap(), submissions);
+    }
+}
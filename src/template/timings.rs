@@ -1,17 +1,184 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::Error,
+    path::Path,
+    process::Command,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    alloc::{self, AllocStats},
+    config::BenchSettings,
+    get_data_path,
+    statistics::Statistics,
+    Day, Year,
+};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+/// Version of the `timings.json` schema. Files without a version were written before it was introduced (version 1).
+pub const TIMINGS_VERSION: u64 = 2;
+
+fn get_timings_path(year: Year) -> String {
+    get_data_path(year, "timings.json")
+}
+
+/// Format nanoseconds like the runner, e.g. `74.1ms`.
+pub fn format_nanos(nanos: u64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos))
+}
+
+/// Format a number of bytes with binary prefixes, e.g. `1.5 MiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Years with a timings file, most recent first.
+pub fn stored_years() -> Vec<Year> {
+    let Ok(entries) = fs::read_dir("data") else {
+        return vec![];
+    };
+
+    let mut years: Vec<Year> = entries
+        .filter_map(|entry| entry.ok()?.file_name().to_str()?.parse().ok())
+        .filter(|year| Path::new(&get_timings_path(*year)).exists())
+        .collect();
+    years.sort_unstable_by(|a, b| b.cmp(a));
+    years
+}
 
 /// Represents benchmark times for a single day.
+/// `parse`, `part_1` and `part_2` hold the median duration in nanoseconds, `total_nanos` is the sum of the medians.
+/// `parse` is only set for solutions with a separate parse step.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub parse: Option<u64>,
+    pub part_1: Option<u64>,
+    pub part_2: Option<u64>,
+    pub parse_stats: Option<Statistics>,
+    pub part_1_stats: Option<Statistics>,
+    pub part_2_stats: Option<Statistics>,
+    /// Settings the day was benched with.
+    pub bench: Option<BenchSettings>,
+    /// Allocations of the first run of each step, recorded with the `alloc-metrics` feature.
+    pub parse_alloc: Option<AllocStats>,
+    pub part_1_alloc: Option<AllocStats>,
+    pub part_2_alloc: Option<AllocStats>,
     pub total_nanos: f64,
+    /// When and where the timing was stored. Timings stored by earlier versions do not have it.
+    pub meta: Option<TimingMeta>,
+}
+
+impl Timing {
+    /// An empty timing of a day, to be filled in step by step.
+    pub fn new(day: Day) -> Self {
+        Timing {
+            day,
+            parse: None,
+            part_1: None,
+            part_2: None,
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            bench: None,
+            parse_alloc: None,
+            part_1_alloc: None,
+            part_2_alloc: None,
+            total_nanos: 0_f64,
+            meta: None,
+        }
+    }
+
+    /// Peak heap usage of the day in bytes: the highest peak of its steps, if allocations were counted.
+    pub fn peak_bytes(&self) -> Option<u64> {
+        [&self.parse_alloc, &self.part_1_alloc, &self.part_2_alloc]
+            .into_iter()
+            .filter_map(|alloc| alloc.map(|a| a.peak_bytes))
+            .max()
+    }
+}
+
+/// Describes the run a stored timing was taken in.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TimingMeta {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Short hash of the git commit, if run in a git repository.
+    pub commit: Option<String>,
+    /// The `AOC_HOST` environment variable, or the host name.
+    pub host: Option<String>,
+    /// Name of a baseline, set with `--label`.
+    pub label: Option<String>,
+}
+
+impl TimingMeta {
+    /// Metadata of a run made now.
+    pub fn current(label: Option<String>) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            commit: command_output("git", &["rev-parse", "--short", "HEAD"]),
+            host: env::var("AOC_HOST")
+                .ok()
+                .or_else(|| command_output("hostname", &[])),
+            label,
+        }
+    }
+
+    /// Whether a baseline name refers to this run, either by its label or its commit.
+    fn matches(&self, name: &str) -> bool {
+        self.label.as_deref() == Some(name)
+            || self.commit.as_deref().is_some_and(|commit| {
+                !name.is_empty() && (commit.starts_with(name) || name.starts_with(commit))
+            })
+    }
+}
+
+fn command_output(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(cmd).args(args).output().ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    let stdout = stdout.trim();
+    (output.status.success() && !stdout.is_empty()).then(|| stdout.to_string())
+}
+
+/// The change of a median between a baseline and a new timing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub day: Day,
+    /// `0` for the parse step.
+    pub part: u8,
+    pub before_nanos: u64,
+    pub after_nanos: u64,
+}
+
+impl Change {
+    /// The change in percent, positive if the part got slower.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn percent(&self) -> f64 {
+        let before = self.before_nanos.max(1) as f64;
+        (self.after_nanos as f64 - before) / before * 100.0
+    }
+
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.percent() > threshold_percent
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -22,42 +189,108 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        fs::create_dir_all(get_data_path(year, ""))?;
+        let mut file = fs::File::create(get_timings_path(year))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Rehydrate timings of a year from a JSON file. If not present, returns empty timings.
+    /// Files written with an earlier schema are migrated.
+    pub fn read_from_file(year: Year) -> Result<Self, String> {
+        let path = get_timings_path(year);
+
+        match fs::read_to_string(&path) {
+            Ok(s) => Timings::try_from(s).map_err(|e| format!("\"{path}\": {e}")),
+            Err(_) => Ok(Timings::default()),
+        }
     }
 
-    /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Merge two sets of timings. The timings of `new` are added to the history after the ones of `self`.
     pub fn merge(&self, new: &Self) -> Self {
+        let mut data: Vec<Timing> = self.data.clone();
+        data.extend(new.data.iter().cloned());
+
+        // keep the order of the history of a day.
+        data.sort_by_key(|t| t.day);
+        Timings { data }
+    }
+
+    /// The most recent timing of each day.
+    pub fn latest(&self) -> Self {
+        self.baseline(None)
+    }
+
+    /// The most recent timing of each day that belongs to the named baseline, i.e. has its label or commit.
+    /// Without a name, the most recent timing of each day is used.
+    pub fn baseline(&self, name: Option<&str>) -> Self {
         let mut data: Vec<Timing> = vec![];
 
-        for timing in &new.data {
-            data.push(timing.clone());
+        for timing in &self.data {
+            let is_match =
+                name.is_none_or(|name| timing.meta.as_ref().is_some_and(|meta| meta.matches(name)));
+
+            if !is_match {
+                continue;
+            }
+
+            match data.iter_mut().find(|t| t.day == timing.day) {
+                Some(existing) => *existing = timing.clone(),
+                None => data.push(timing.clone()),
+            }
         }
 
+        Timings { data }
+    }
+
+    /// Compare the medians of `self` with a baseline. Parts without a timing on either side are skipped.
+    pub fn compare(&self, baseline: &Self) -> Vec<Change> {
+        let mut changes = vec![];
+
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
-                data.push(timing.clone());
+            let Some(before) = baseline.data.iter().rev().find(|t| t.day == timing.day) else {
+                continue;
+            };
+
+            let parts = [
+                (0, before.parse, timing.parse),
+                (1, before.part_1, timing.part_1),
+                (2, before.part_2, timing.part_2),
+            ];
+
+            for (part, before, after) in parts {
+                if let (Some(before_nanos), Some(after_nanos)) = (before, after) {
+                    changes.push(Change {
+                        day: timing.day,
+                        part,
+                        before_nanos,
+                        after_nanos,
+                    });
+                }
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        changes
     }
 
-    /// Sum up total duration of timings as millis.
+    /// Set the metadata of all timings.
+    pub fn with_meta(mut self, meta: &TimingMeta) -> Self {
+        for timing in &mut self.data {
+            timing.meta = Some(meta.clone());
+        }
+        self
+    }
+
+    /// Sum up total duration of the most recent timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        self.latest()
+            .data
+            .iter()
+            .map(|x| x.total_nanos)
+            .sum::<f64>()
+            / 1_000_000_f64
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
@@ -73,6 +306,8 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("version".into(), JsonValue::Number(TIMINGS_VERSION as f64));
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -88,21 +323,91 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let version = match json.get("version") {
+            Some(v) => *v
+                .get::<f64>()
+                .ok_or("expected `json.version` to be a number.")? as u64,
+            None => 1,
+        };
+
+        if version > TIMINGS_VERSION {
+            return Err(format!(
+                "timings have version {version}, but only versions up to {TIMINGS_VERSION} are supported. Please update the template."
+            ));
+        }
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
-        Ok(Timings {
-            data: json_data
-                .iter()
-                .map(Timing::try_from)
-                .collect::<Result<_, _>>()?,
-        })
+        let data = json_data
+            .iter()
+            .map(|timing| {
+                if version == 1 {
+                    Timing::try_from(&migrate_v1(timing)?)
+                } else {
+                    Timing::try_from(timing)
+                }
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Timings { data })
+    }
+}
+
+/// Migrate a timing of version 1, which stored the parts as display strings like `74.13ns`.
+fn migrate_v1(value: &JsonValue) -> Result<JsonValue, String> {
+    let mut json = value
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("Expected timing to be a JSON object.")?
+        .clone();
+
+    for key in ["parse", "part_1", "part_2"] {
+        let nanos = match json.remove(key) {
+            // parse timings are optional, solutions without a parse step do not have them.
+            None if key == "parse" => JsonValue::Null,
+            None => return Err(format!("Expected timing.{key} to be null or string.")),
+            Some(JsonValue::Null) => JsonValue::Null,
+            Some(JsonValue::String(s)) =>
+            {
+                #[allow(clippy::cast_precision_loss)]
+                JsonValue::Number(
+                    parse_duration(&s)
+                        .ok_or(format!("Expected timing.{key} `{s}` to be a duration."))?
+                        as f64,
+                )
+            }
+            Some(_) => return Err(format!("Expected timing.{key} to be null or string.")),
+        };
+
+        json.insert(format!("{key}_nanos"), nanos);
     }
+
+    Ok(JsonValue::Object(json))
+}
+
+/// Parse a duration as formatted by `Debug`, e.g. `74.13ns` or `1.2s`, to nanoseconds.
+fn parse_duration(s: &str) -> Option<u64> {
+    let unit_pos = s.find(|c: char| c.is_alphabetic())?;
+    let value: f64 = s[..unit_pos].trim().parse().ok()?;
+
+    let factor = match &s[unit_pos..] {
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Some((value * factor).round() as u64)
 }
 
 /* -------------------------------------------------------------------------- */
@@ -114,25 +419,63 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        #[allow(clippy::cast_precision_loss)]
+        let nanos =
+            |value: Option<u64>| value.map_or(JsonValue::Null, |x| JsonValue::Number(x as f64));
+
+        map.insert("parse_nanos".into(), nanos(value.parse));
+        map.insert("part_1_nanos".into(), nanos(value.part_1));
+        map.insert("part_2_nanos".into(), nanos(value.part_2));
 
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "parse_stats".into(),
+            value
+                .parse_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
         );
 
         map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "part_1_stats".into(),
+            value
+                .part_1_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_stats".into(),
+            value
+                .part_2_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
         );
 
+        map.insert(
+            "bench".into(),
+            value
+                .bench
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        let allocations =
+            |value: &Option<AllocStats>| value.as_ref().map_or(JsonValue::Null, JsonValue::from);
+        map.insert("parse_alloc".into(), allocations(&value.parse_alloc));
+        map.insert("part_1_alloc".into(), allocations(&value.part_1_alloc));
+        map.insert("part_2_alloc".into(), allocations(&value.part_2_alloc));
+
+        if let Some(meta) = &value.meta {
+            #[allow(clippy::cast_precision_loss)]
+            map.insert("timestamp".into(), JsonValue::Number(meta.timestamp as f64));
+
+            let optional =
+                |value: &Option<String>| value.clone().map_or(JsonValue::Null, JsonValue::String);
+            map.insert("commit".into(), optional(&meta.commit));
+            map.insert("host".into(), optional(&meta.host));
+            map.insert("label".into(), optional(&meta.label));
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -151,30 +494,88 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let nanos = |key: &str| match json.get(key) {
+            Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::Number(x)) if *x >= 0.0 => Ok(Some(*x as u64)),
+            _ => Err(format!("Expected timing.{key} to be null or a number.")),
+        };
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        let part_1 = nanos("part_1_nanos")?;
+        let part_2 = nanos("part_2_nanos")?;
+        let parse = nanos("parse_nanos")?;
 
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // statistics are optional, timings stored by earlier versions do not have them.
+        let parse_stats = parse_statistics(json.get("parse_stats"))?;
+        let part_1_stats = parse_statistics(json.get("part_1_stats"))?;
+        let part_2_stats = parse_statistics(json.get("part_2_stats"))?;
+
+        let bench = match json.get("bench") {
+            Some(v) if !v.is_null() => Some(BenchSettings::try_from(v)?),
+            _ => None,
+        };
+
+        // allocations are only counted with the `alloc-metrics` feature.
+        let parse_alloc = alloc::parse_alloc(json.get("parse_alloc"))?;
+        let part_1_alloc = alloc::parse_alloc(json.get("part_1_alloc"))?;
+        let part_2_alloc = alloc::parse_alloc(json.get("part_2_alloc"))?;
+
+        // metadata is optional, timings stored by earlier versions do not have it.
+        let meta = match json.get("timestamp") {
+            Some(v) => {
+                let string = |key: &str| match json.get(key) {
+                    Some(v) if !v.is_null() => v
+                        .get::<String>()
+                        .cloned()
+                        .map(Some)
+                        .ok_or(format!("Expected timing.{key} to be null or string.")),
+                    _ => Ok(None),
+                };
+
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                Some(TimingMeta {
+                    timestamp: v
+                        .get::<f64>()
+                        .map(|x| *x as u64)
+                        .ok_or("Expected timing.timestamp to be a number.")?,
+                    commit: string("commit")?,
+                    host: string("host")?,
+                    label: string("label")?,
+                })
+            }
+            None => None,
+        };
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse,
+            part_1,
+            part_2,
+            parse_stats,
+            part_1_stats,
+            part_2_stats,
+            bench,
+            parse_alloc,
+            part_1_alloc,
+            part_2_alloc,
             total_nanos,
+            meta,
         })
     }
 }
 
+fn parse_statistics(value: Option<&JsonValue>) -> Result<Option<Statistics>, String> {
+    match value {
+        Some(v) if !v.is_null() => Statistics::try_from(v).map(Some),
+        _ => Ok(None),
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
@@ -187,22 +588,21 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(10_000_000),
+                    part_2: Some(20_000_000),
                     total_nanos: 3e+10,
+                    ..Timing::new(day!(1))
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(30_000_000),
+                    part_2: Some(40_000_000),
                     total_nanos: 7e+10,
+                    ..Timing::new(day!(2))
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
+                    part_1: Some(40_000_000),
                     total_nanos: 4e+10,
+                    ..Timing::new(day!(4))
                 },
             ],
         }
@@ -218,9 +618,73 @@ mod tests {
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_1, Some(1_000_000));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.part_1_stats.is_none(), true);
+        }
+
+        #[test]
+        fn handles_json_timings_with_statistics() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "min": 900000, "median": 1000000, "mean": 1100000, "p95": 1500000, "stddev": 2000, "samples": 100, "outliers": 3 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.median, 1_000_000_f64);
+            assert_eq!(stats.outliers, 3);
+            assert_eq!(timing.part_2_stats.is_none(), true);
+            assert_eq!(timing.bench.is_none(), true);
+            assert_eq!(timing.parse.is_none(), true);
+        }
+
+        #[test]
+        fn handles_json_timings_with_parse() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "5ms", "part_1": "1ms", "part_2": "2ms", "total_nanos": 8000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse, Some(5_000_000));
+            assert_eq!(timing.parse_stats.is_none(), true);
+        }
+
+        #[test]
+        fn handles_json_timings_with_bench_settings() {
+            let json = r#"{ "data": [{ "day": "06", "part_1": "1s", "part_2": null, "total_nanos": 1000000000, "bench": { "warm_up_ms": 100, "measurement_ms": 5000, "min_samples": 10, "max_samples": 20 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let bench = timings.data.first().unwrap().bench.unwrap();
+            assert_eq!(bench.measurement_time.as_millis(), 5000);
+            assert_eq!(bench.max_samples, 20);
+        }
+
+        #[test]
+        fn migrates_display_strings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "74.13ns", "part_2": "1.5µs", "total_nanos": 1574 }, { "day": "02", "part_1": "2.1s", "part_2": "3.0ms", "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].part_1, Some(74));
+            assert_eq!(timings.data[0].part_2, Some(1_500));
+            assert_eq!(timings.data[1].part_1, Some(2_100_000_000));
+            assert_eq!(timings.data[1].part_2, Some(3_000_000));
+        }
+
+        #[test]
+        fn handles_versioned_timings() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "parse_nanos": null, "part_1_nanos": 74, "part_2_nanos": null, "total_nanos": 74 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].part_1, Some(74));
+            assert_eq!(timings.data[0].part_2, None);
+        }
+
+        #[test]
+        fn rejects_newer_versions() {
+            let json = r#"{ "version": 3, "data": [] }"#.to_string();
+            assert!(Timings::try_from(json)
+                .unwrap_err()
+                .contains("only versions up to 2"));
+        }
+
+        #[test]
+        fn rejects_malformed_durations() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "fast", "part_2": null, "total_nanos": 0 }] }"#.to_string();
+            assert!(Timings::try_from(json).is_err());
         }
 
         #[test]
@@ -247,9 +711,48 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::alloc::AllocStats;
+        use crate::template::timings::{format_bytes, Timings};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
+        #[test]
+        fn roundtrips_timings() {
+            let json = JsonValue::from(get_mock_timings()).stringify().unwrap();
+            assert!(json.contains(r#""version":2"#));
+
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data.len(), 3);
+            assert_eq!(timings.data[0].part_1, Some(10_000_000));
+            assert_eq!(timings.data[2].part_2, None);
+        }
+
+        #[test]
+        fn roundtrips_allocations() {
+            let mut timings = get_mock_timings();
+            let alloc = |peak_bytes: u64| AllocStats {
+                bytes: 4096,
+                count: 12,
+                peak_bytes,
+            };
+            timings.data[0].part_1_alloc = Some(alloc(2048));
+            timings.data[0].part_2_alloc = Some(alloc(1024));
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].part_1_alloc, Some(alloc(2048)));
+            assert_eq!(timings.data[0].parse_alloc, None);
+            assert_eq!(timings.data[0].peak_bytes(), Some(2048));
+            assert_eq!(timings.data[1].peak_bytes(), None);
+        }
+
+        #[test]
+        fn formats_bytes() {
+            assert_eq!(format_bytes(512), "512 B");
+            assert_eq!(format_bytes(1536), "1.5 KiB");
+            assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+        }
+
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
@@ -278,42 +781,128 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1: Some(1_000_000),
+                    part_2: Some(2_000_000),
                     total_nanos: 3_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
+                    part_1: Some(1_000_000),
                     total_nanos: 1_000_000_000_f64,
+                    ..Timing::new(day!(1))
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![Timing::new(day!(1))],
+            };
+
+            assert_eq!(timings.is_day_complete(day!(1)), false);
+        }
+    }
+
+    mod history {
+        use std::collections::HashMap;
+
+        use tinyjson::JsonValue;
+
+        use crate::{
+            day,
+            template::{
+                statistics::Statistics,
+                timings::{Timing, TimingMeta, Timings},
+            },
+        };
+
+        fn timing(day: u8, part_1_nanos: f64, commit: &str, label: Option<&str>) -> Timing {
+            let day = crate::template::Day::new(day).unwrap();
+            Timing {
+                part_1: Some(part_1_nanos as u64),
+                part_1_stats: Some(Statistics::from_samples(&[
+                    std::time::Duration::from_nanos(part_1_nanos as u64),
+                ])),
+                total_nanos: part_1_nanos,
+                meta: Some(TimingMeta {
+                    timestamp: 1_700_000_000,
+                    commit: Some(commit.into()),
+                    host: Some("ci".into()),
+                    label: label.map(String::from),
+                }),
+                ..Timing::new(day)
+            }
+        }
+
+        fn get_history() -> Timings {
+            Timings {
+                data: vec![
+                    timing(1, 100.0, "aaaaaaa", Some("v1")),
+                    timing(2, 100.0, "aaaaaaa", Some("v1")),
+                    timing(1, 200.0, "bbbbbbb", None),
+                ],
+            }
+        }
+
+        #[test]
+        fn selects_baselines() {
+            let history = get_history();
+
+            let previous = history.baseline(None);
+            assert_eq!(previous.data.len(), 2);
+            assert_eq!(previous.data[0].total_nanos, 200.0);
+
+            let v1 = history.baseline(Some("v1"));
+            assert_eq!(v1.data[0].total_nanos, 100.0);
+
+            let by_commit = history.baseline(Some("bbbb"));
+            assert_eq!(by_commit.data.len(), 1);
+
+            assert!(history.baseline(Some("unknown")).data.is_empty());
+        }
+
+        #[test]
+        fn compares_with_baselines() {
+            let new = Timings {
+                data: vec![timing(1, 150.0, "ccccccc", None)],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            let changes = new.compare(&get_history().baseline(Some("v1")));
+            assert_eq!(changes.len(), 1);
+            assert_eq!(changes[0].part, 1);
+            assert_eq!(changes[0].percent(), 50.0);
+            assert!(changes[0].is_regression(10.0));
+
+            let changes = new.compare(&get_history().latest());
+            assert_eq!(changes[0].percent(), -25.0);
+            assert!(!changes[0].is_regression(10.0));
+        }
+
+        #[test]
+        fn roundtrips_metadata() {
+            let json = JsonValue::from(get_history()).stringify().unwrap();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].meta, get_history().data[0].meta);
+
+            let value = JsonValue::from(get_history());
+            let first = &value.get::<HashMap<String, JsonValue>>().unwrap()["data"]
+                .get::<Vec<JsonValue>>()
+                .unwrap()[0];
+            assert!(first
+                .get::<HashMap<String, JsonValue>>()
+                .unwrap()
+                .contains_key("commit"));
         }
     }
 
@@ -329,12 +918,7 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(3))],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -349,20 +933,23 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::new(day!(2))],
             };
             let merged = timings.merge(&other);
 
-            assert_eq!(merged.data.len(), 3);
-            assert_eq!(merged.data[0].day, day!(1));
+            // the previous timing of day 2 is kept in the history.
+            assert_eq!(merged.data.len(), 4);
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
-            assert_eq!(merged.data[2].day, day!(4));
+            assert_eq!(merged.data[1].total_nanos, 7e+10);
+            assert_eq!(merged.data[2].day, day!(2));
+            assert_eq!(merged.data[2].total_nanos, 0_f64);
+
+            let latest = merged.latest();
+            assert_eq!(latest.data.len(), 3);
+            assert_eq!(latest.data[0].day, day!(1));
+            assert_eq!(latest.data[1].day, day!(2));
+            assert_eq!(latest.data[1].total_nanos, 0_f64);
+            assert_eq!(latest.data[2].day, day!(4));
         }
 
         #[test]
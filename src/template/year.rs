@@ -0,0 +1,173 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::Day;
+
+#[cfg(feature = "today")]
+use chrono::{Datelike, FixedOffset, Utc};
+
+#[cfg(feature = "today")]
+const SERVER_UTC_OFFSET: i32 = -5;
+
+/// The year of the first advent of code event.
+const FIRST_YEAR: u16 = 2015;
+
+/// The first event that has 12 days instead of 25.
+const FIRST_SHORT_YEAR: u16 = 2025;
+
+/// A valid year of advent (i.e. an integer starting from 2015).
+/// Solutions, inputs, examples, puzzles and timings are namespaced by year.
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2024).unwrap();
+/// assert_eq!(year.to_string(), "2024")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(FIRST_YEAR..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Returns the year set in the `AOC_YEAR` environment variable, if valid.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    // Not part of the public API
+    /// Reads the year from the path of a solution file, e.g. `src/bin/2024-01.rs`. Panics if the file is named differently.
+    #[doc(hidden)]
+    pub const fn __from_path(path: &str) -> Self {
+        let bytes = path.as_bytes();
+
+        // find the start of the file name.
+        let mut start = bytes.len();
+        while start > 0 && bytes[start - 1] != b'/' && bytes[start - 1] != b'\\' {
+            start -= 1;
+        }
+
+        assert!(
+            bytes.len() - start > 5 && bytes[start + 4] == b'-',
+            "solution files need to be named `src/bin/<year>-<day>.rs`"
+        );
+
+        let mut year: u16 = 0;
+        let mut i = start;
+        while i < start + 4 {
+            assert!(
+                bytes[i].is_ascii_digit(),
+                "solution files need to be named `src/bin/<year>-<day>.rs`"
+            );
+            year = year * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        assert!(year >= FIRST_YEAR, "invalid year, expecting 2015 or later");
+        Self(year)
+    }
+
+    /// Returns the last day of the event, `25` until 2024 and `12` from 2025 onward.
+    pub const fn last_day(self) -> u8 {
+        if self.0 >= FIRST_SHORT_YEAR {
+            12
+        } else {
+            25
+        }
+    }
+
+    /// Returns `true` if the day is part of the event of this year.
+    pub fn has_day(self, day: Day) -> bool {
+        day <= self.last_day()
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+}
+
+#[cfg(feature = "today")]
+impl Year {
+    /// Returns the current year in the timezone of the advent of code server.
+    pub fn today() -> Option<Self> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let today = Utc::now().with_timezone(&offset);
+        Self::new(u16::try_from(today.year()).ok()?)
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year, starting from 2015")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+    use crate::day;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2024".parse::<Year>().unwrap(), Year(2024));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("24".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn reads_year_from_path() {
+        assert_eq!(Year::__from_path("src/bin/2024-01.rs"), Year(2024));
+        assert_eq!(Year::__from_path("/repo/src/bin/2015-25.rs"), Year(2015));
+        assert_eq!(Year::__from_path("src\\bin\\2023-07.rs"), Year(2023));
+    }
+
+    #[test]
+    fn limits_days_per_year() {
+        assert_eq!(Year(2024).last_day(), 25);
+        assert_eq!(Year(2025).last_day(), 12);
+        assert!(Year(2024).has_day(day!(25)));
+        assert!(Year(2025).has_day(day!(12)));
+        assert!(!Year(2025).has_day(day!(13)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_unnamespaced_paths() {
+        Year::__from_path("src/bin/01.rs");
+    }
+}
@@ -0,0 +1,525 @@
+//! Download, read and submit flows, run against a local stand-in for the advent of code website.
+#![cfg(feature = "test_lib")]
+
+mod support;
+
+use std::time::Duration;
+
+use advent_of_code::template::aoc_client::{AocClient, AocClientError, SubmitOutcome};
+use advent_of_code::template::{Day, Year};
+use support::{run_bin, stdout, FakeAoc, SubmitResponse, TempDir, SESSION};
+
+const MAIN_BIN: &str = env!("CARGO_BIN_EXE_advent_of_code");
+const SOLUTION_BIN: &str = env!("CARGO_BIN_EXE_2024-01");
+
+const PUZZLE: &str = r#"<!DOCTYPE html>
+<html lang="en-us"><body><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The list:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<p>What is the <em>total distance</em> between your lists?</p>
+</article>
+</main></body></html>"#;
+
+const INPUT: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+fn year() -> Year {
+    Year::new(2024).unwrap()
+}
+
+fn day() -> Day {
+    Day::new(1).unwrap()
+}
+
+fn get_server() -> FakeAoc {
+    let server = FakeAoc::start();
+    server.add_puzzle(2024, 1, PUZZLE);
+    server.add_input(2024, 1, INPUT);
+    server
+}
+
+fn get_client(server: &FakeAoc) -> AocClient {
+    AocClient::new(SESSION).with_base_url(server.url())
+}
+
+#[test]
+fn client_fetches_input_and_puzzle() {
+    let server = get_server();
+    let client = get_client(&server);
+
+    assert_eq!(client.get_input(year(), day()).unwrap(), INPUT);
+    assert_eq!(client.get_puzzle(year(), day()).unwrap(), PUZZLE);
+
+    let requests = server.requests();
+    assert_eq!(requests[0].path, "/2024/day/1/input");
+    assert_eq!(requests[1].path, "/2024/day/1");
+    assert_eq!(requests[0].cookie.as_deref(), Some("session=test-session"));
+}
+
+#[test]
+fn client_reports_rejected_sessions_and_locked_puzzles() {
+    let server = get_server();
+
+    let client = AocClient::new("expired").with_base_url(server.url());
+    assert!(matches!(
+        client.get_input(year(), day()),
+        Err(AocClientError::Unauthorized)
+    ));
+
+    let client = get_client(&server);
+    assert!(matches!(
+        client.get_puzzle(year(), Day::new(2).unwrap()),
+        Err(AocClientError::NotFound)
+    ));
+}
+
+#[test]
+fn client_submits_answers() {
+    let server = get_server();
+    let client = get_client(&server);
+
+    let cases = [
+        (SubmitResponse::Correct, SubmitOutcome::Correct),
+        (SubmitResponse::Wrong, SubmitOutcome::Incorrect),
+        (SubmitResponse::TooHigh, SubmitOutcome::TooHigh),
+        (SubmitResponse::TooLow, SubmitOutcome::TooLow),
+        (
+            SubmitResponse::RateLimited("1m 5s"),
+            SubmitOutcome::RateLimited(Duration::from_secs(65)),
+        ),
+        (SubmitResponse::AlreadySolved, SubmitOutcome::AlreadySolved),
+    ];
+
+    for (response, expected) in cases {
+        server.respond_to_submit(response);
+        assert_eq!(client.submit(year(), day(), 2, "31\n").unwrap(), expected);
+    }
+
+    // the level is the part, the answer is sent trimmed.
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.method, "POST");
+    assert_eq!(request.path, "/2024/day/1/answer");
+    assert_eq!(request.body, "level=2&answer=31");
+}
+
+#[test]
+fn scaffold_downloads_input_and_puzzle() {
+    let server = get_server();
+    let dir = TempDir::new("scaffold");
+    dir.write("src/bin/.keep", "");
+
+    let output = run_bin(MAIN_BIN, &dir, &server, &["scaffold", "1", "--download"]);
+    assert!(output.status.success(), "{}", stdout(&output));
+
+    assert_eq!(dir.read("data/2024/inputs/01.txt"), INPUT);
+    assert_eq!(dir.read("data/2024/examples/01.txt"), "");
+    assert!(dir.read("src/bin/2024-01.rs").contains("solution!(1)"));
+    assert_eq!(
+        dir.read("data/2024/puzzles/01.md"),
+        "## --- Day 1: Historian Hysteria ---\n\n\
+         The list:\n\n\
+         ```\n3   4\n4   3\n```\n\n\
+         What is the *total distance* between your lists?\n"
+    );
+}
+
+#[test]
+fn read_prints_puzzle() {
+    let server = get_server();
+    let dir = TempDir::new("read");
+
+    let output = run_bin(MAIN_BIN, &dir, &server, &["read", "1"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("## --- Day 1: Historian Hysteria ---"));
+
+    let output = run_bin(MAIN_BIN, &dir, &server, &["read", "2"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn solve_submits_and_records_correct_answers() {
+    let server = get_server();
+    let dir = TempDir::new("submit-correct");
+    dir.write("data/2024/inputs/01.txt", INPUT);
+
+    server.respond_to_submit(SubmitResponse::Correct);
+
+    let output = run_bin(SOLUTION_BIN, &dir, &server, &["--submit", "1"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("That's the right answer!"));
+
+    let request = server
+        .requests()
+        .into_iter()
+        .find(|r| r.method == "POST")
+        .unwrap();
+    assert_eq!(request.body, "level=1&answer=11");
+
+    assert!(dir.read("data/2024/answers.json").contains("\"11\""));
+}
+
+#[test]
+fn solve_downloads_part_two_after_part_one_is_solved() {
+    let server = get_server();
+    server.add_puzzle(
+        2024,
+        1,
+        "<main><article><h2>--- Day 1: Test ---</h2><p>Total: <code><em>11</em></code>.</p></article>\
+         <article><h2>--- Part Two ---</h2><p>For example:</p><pre><code>1 1\n</code></pre>\
+         <p>Similarity: <code><em>31</em></code>.</p></article></main>",
+    );
+    let dir = TempDir::new("submit-unlock");
+    dir.write("data/2024/inputs/01.txt", INPUT);
+    dir.write("data/2024/examples/01.txt", INPUT);
+    dir.write("data/2024/puzzles/01.md", "## --- Day 1: Test ---\n");
+
+    server.respond_to_submit(SubmitResponse::Correct);
+
+    let output = run_bin(
+        SOLUTION_BIN,
+        &dir,
+        &server,
+        &["--submit", "1", "--examples"],
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = stdout(&output);
+    assert!(stdout.contains("## --- Part Two ---"));
+    assert!(!stdout.contains("## --- Day 1: Test ---"));
+
+    assert!(dir.read("data/2024/puzzles/01.md").contains("Similarity"));
+    assert_eq!(dir.read("data/2024/examples/01-1.txt"), INPUT);
+    assert_eq!(dir.read("data/2024/examples/01-2.txt"), "1 1\n");
+}
+
+#[test]
+fn solve_reports_wrong_answers() {
+    let server = get_server();
+    let dir = TempDir::new("submit-wrong");
+    dir.write("data/2024/inputs/01.txt", INPUT);
+
+    server.respond_to_submit(SubmitResponse::TooHigh);
+
+    let output = run_bin(SOLUTION_BIN, &dir, &server, &["--submit", "2"]);
+    assert!(stdout(&output).contains("it is too high"));
+
+    let request = server.requests().pop().unwrap();
+    assert_eq!(request.body, "level=2&answer=31");
+
+    assert!(!dir.path().join("data/2024/answers.json").exists());
+}
+
+#[test]
+fn solve_refuses_answers_that_were_rejected() {
+    let server = get_server();
+    let dir = TempDir::new("submit-rejected");
+    dir.write("data/2024/inputs/01.txt", INPUT);
+
+    server.respond_to_submit(SubmitResponse::Wrong);
+    let output = run_bin(SOLUTION_BIN, &dir, &server, &["--submit", "1"]);
+    assert!(output.status.success());
+    assert!(dir
+        .read("data/2024/submissions.json")
+        .contains("\"incorrect\""));
+
+    let output = run_bin(SOLUTION_BIN, &dir, &server, &["--submit", "1"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Refusing to submit"));
+
+    let posts = server
+        .requests()
+        .iter()
+        .filter(|r| r.method == "POST")
+        .count();
+    assert_eq!(posts, 1);
+}
+
+#[test]
+fn solve_warns_about_example_answers() {
+    let server = get_server();
+    let dir = TempDir::new("submit-example");
+    dir.write("data/2024/inputs/01.txt", INPUT);
+    dir.write("data/2024/examples/01.txt", INPUT);
+
+    server.respond_to_submit(SubmitResponse::Wrong);
+    let output = run_bin(SOLUTION_BIN, &dir, &server, &["--submit", "1"]);
+    assert!(stdout(&output).contains("the answer equals the answer for the example input"));
+}
+
+#[test]
+fn solve_fails_fast_when_rate_limited() {
+    let server = get_server();
+    let dir = TempDir::new("submit-no-wait");
+    dir.write("data/2024/inputs/01.txt", INPUT);
+
+    server.respond_to_submit(SubmitResponse::RateLimited("5m 0s"));
+    let output = run_bin(SOLUTION_BIN, &dir, &server, &["--submit", "1", "--no-wait"]);
+    assert!(!output.status.success());
+    assert!(stdout(&output).contains("You gave an answer too recently"));
+
+    // the cooldown is known from the submissions log, the website is not asked again.
+    let output = run_bin(SOLUTION_BIN, &dir, &server, &["--submit", "1", "--no-wait"]);
+    assert!(!output.status.success());
+
+    let posts = server
+        .requests()
+        .iter()
+        .filter(|r| r.method == "POST")
+        .count();
+    assert_eq!(posts, 1);
+}
+
+#[test]
+fn solve_waits_and_resubmits_when_rate_limited() {
+    let server = get_server();
+    let dir = TempDir::new("submit-wait");
+    dir.write("data/2024/inputs/01.txt", INPUT);
+
+    server.respond_to_submit(SubmitResponse::RateLimited("0s"));
+    server.respond_to_submit(SubmitResponse::Correct);
+
+    let output = run_bin(SOLUTION_BIN, &dir, &server, &["--submit", "1"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("That's the right answer!"));
+
+    let posts = server
+        .requests()
+        .iter()
+        .filter(|r| r.method == "POST")
+        .count();
+    assert_eq!(posts, 2);
+}
+
+#[test]
+fn scaffold_extracts_examples() {
+    let server = get_server();
+    let dir = TempDir::new("scaffold-examples");
+    dir.write("src/bin/.keep", "");
+
+    let output = run_bin(
+        MAIN_BIN,
+        &dir,
+        &server,
+        &["scaffold", "1", "--download", "--examples"],
+    );
+    assert!(output.status.success());
+    assert_eq!(dir.read("data/2024/examples/01.txt"), "3   4\n4   3\n");
+}
+
+#[test]
+fn examples_extracts_an_example_per_part() {
+    let server = get_server();
+    let dir = TempDir::new("examples");
+    dir.write(
+        "data/2024/puzzles/01.md",
+        "## --- Day 1: Test ---\n\nA grid:\n\n```\n#.#\n```\n\n\
+         For example:\n\n```\n1 2 \n3 4\n```\n\n\
+         Here is a larger example:\n\n```\n1 2 3\n```\n\n\
+         ## --- Part Two ---\n\nFor example:\n\n```\n5\n```\n",
+    );
+    dir.write("data/2024/examples/01.txt", "pasted by hand\n");
+
+    // examples are not overwritten by default.
+    let output = run_bin(MAIN_BIN, &dir, &server, &["examples", "1"]);
+    assert!(output.status.success());
+    assert_eq!(dir.read("data/2024/examples/01.txt"), "pasted by hand\n");
+
+    // without a terminal, the first of several examples is used.
+    let output = run_bin(MAIN_BIN, &dir, &server, &["examples", "1", "--overwrite"]);
+    assert!(output.status.success());
+    assert_eq!(dir.read("data/2024/examples/01.txt"), "1 2 \n3 4\n");
+    // both parts get a file for `read_file_part`.
+    assert_eq!(dir.read("data/2024/examples/01-1.txt"), "1 2 \n3 4\n");
+    assert_eq!(dir.read("data/2024/examples/01-2.txt"), "5\n");
+}
+
+#[test]
+fn scaffold_fills_in_example_answers() {
+    let server = get_server();
+    server.add_puzzle(
+        2024,
+        1,
+        "<main><article><h2>--- Day 1: Test ---</h2>\
+         <p>A distance of <code><em>2</em></code>, a total distance of <code><em>11</em></code>.</p>\
+         </article></main>",
+    );
+    let dir = TempDir::new("scaffold-answers");
+    dir.write("src/bin/.keep", "");
+
+    let output = run_bin(MAIN_BIN, &dir, &server, &["scaffold", "1", "--download"]);
+    assert!(output.status.success());
+
+    let module = dir.read("src/bin/2024-01.rs");
+    assert!(module.contains("assert_eq!(result, Some(11));"));
+    assert!(module.contains("assert_eq!(result, None);"));
+
+    // scaffolding again after part two unlocked keeps the solution and the example.
+    dir.write(
+        "src/bin/2024-01.rs",
+        &module.replace("None\n}", "Some(1)\n}"),
+    );
+    dir.write("data/2024/examples/01.txt", "pasted by hand\n");
+    server.add_puzzle(
+        2024,
+        1,
+        "<main><article><h2>--- Day 1: Test ---</h2>\
+         <p>A total distance of <code><em>11</em></code>.</p></article>\
+         <article><h2>--- Part Two ---</h2>\
+         <p>A similarity score of <code><em>31</em></code>.</p></article></main>",
+    );
+
+    let output = run_bin(MAIN_BIN, &dir, &server, &["scaffold", "1", "--download"]);
+    assert!(output.status.success());
+
+    let module = dir.read("src/bin/2024-01.rs");
+    assert!(module.contains("Some(1)\n}"));
+    assert!(module.contains("assert_eq!(result, Some(11));"));
+    assert!(module.contains("assert_eq!(result, Some(31));"));
+    assert_eq!(dir.read("data/2024/examples/01.txt"), "pasted by hand\n");
+}
+
+#[test]
+fn scaffold_uses_named_templates() {
+    let server = get_server();
+    let dir = TempDir::new("scaffold-templates");
+    dir.write("src/bin/.keep", "");
+    dir.write(
+        "templates/custom.txt",
+        "// %TITLE% (%YEAR% day %DAY%)\nadvent_of_code::solution!(%DAY_NUMBER%);\n// %RETURN_TYPE%\n",
+    );
+
+    let output = run_bin(
+        MAIN_BIN,
+        &dir,
+        &server,
+        &["scaffold", "1", "--download", "--template", "custom"],
+    );
+    assert!(output.status.success());
+    assert_eq!(
+        dir.read("src/bin/2024-01.rs"),
+        "// Historian Hysteria (2024 day 01)\nadvent_of_code::solution!(1);\n// u32\n"
+    );
+
+    // the config sets the default template and return type.
+    dir.write(
+        "data/config.json",
+        r#"{ "scaffold": { "template": "custom", "return_type": "u64" } }"#,
+    );
+    let output = run_bin(MAIN_BIN, &dir, &server, &["scaffold", "2"]);
+    assert!(output.status.success());
+    assert_eq!(
+        dir.read("src/bin/2024-02.rs"),
+        "// Day 2 (2024 day 02)\nadvent_of_code::solution!(2);\n// u64\n"
+    );
+
+    let output = run_bin(
+        MAIN_BIN,
+        &dir,
+        &server,
+        &["scaffold", "3", "--template", "grid"],
+    );
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("Available templates: custom, default.")
+    );
+}
+
+#[test]
+fn read_prints_downloaded_puzzle_parts() {
+    let server = get_server();
+    let dir = TempDir::new("read-local");
+    dir.write(
+        "data/2024/puzzles/01.md",
+        "## --- Day 1: Local ---\n\nPart one.\n\n## --- Part Two ---\n\nPart two.\n",
+    );
+
+    let output = run_bin(MAIN_BIN, &dir, &server, &["read", "1"]);
+    assert!(output.status.success());
+    assert!(stdout(&output).contains("## --- Day 1: Local ---"));
+    assert!(server.requests().is_empty());
+
+    let output = run_bin(MAIN_BIN, &dir, &server, &["read", "1", "--part", "1"]);
+    assert_eq!(stdout(&output), "## --- Day 1: Local ---\n\nPart one.\n\n");
+
+    let output = run_bin(MAIN_BIN, &dir, &server, &["read", "1", "--part", "2"]);
+    assert_eq!(stdout(&output), "## --- Part Two ---\n\nPart two.\n");
+
+    let output = run_bin(MAIN_BIN, &dir, &server, &["read", "1", "--part", "3"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn time_exports_stored_timings() {
+    let server = get_server();
+    let dir = TempDir::new("time-export");
+    dir.write(
+        "data/2024/timings.json",
+        r#"{ "version": 2, "data": [{ "day": "01", "parse_nanos": null, "part_1_nanos": 1200, "part_2_nanos": 3500000, "total_nanos": 3501200 }] }"#,
+    );
+    dir.write(
+        "README.md",
+        "# readme\n<!--- benchmarking table --->\n<!--- benchmarking table --->\n",
+    );
+
+    let output = run_bin(
+        MAIN_BIN,
+        &dir,
+        &server,
+        &["time", "--export", "csv", "timings.csv"],
+    );
+    assert!(output.status.success());
+    assert!(dir.read("timings.csv").contains("2024,01,2,3500000,"));
+
+    let output = run_bin(
+        MAIN_BIN,
+        &dir,
+        &server,
+        &["time", "1", "--export", "json", "day.json"],
+    );
+    assert!(output.status.success());
+    assert!(dir.read("day.json").contains("\"part_1_nanos\": 1200"));
+
+    // exporting does not run solutions, so options of a run are rejected.
+    let output = run_bin(
+        MAIN_BIN,
+        &dir,
+        &server,
+        &["time", "--store", "--export", "csv", "stored.csv"],
+    );
+    assert!(!output.status.success());
+    assert!(!dir.path().join("stored.csv").exists());
+
+    let output = run_bin(
+        MAIN_BIN,
+        &dir,
+        &server,
+        &[
+            "time",
+            "--export",
+            "svg",
+            "data/2024/benchmarks.svg",
+            "--readme",
+        ],
+    );
+    assert!(output.status.success());
+    assert!(dir
+        .read("data/2024/benchmarks.svg")
+        .contains("<title>Day 01 Part 2: 3.5ms</title>"));
+    assert!(dir
+        .read("README.md")
+        .contains("![Benchmarks chart](./data/2024/benchmarks.svg)"));
+
+    let output = run_bin(
+        MAIN_BIN,
+        &dir,
+        &server,
+        &["time", "--export", "csv", "out.csv", "--readme"],
+    );
+    assert!(!output.status.success());
+    assert!(server.requests().is_empty());
+}
@@ -0,0 +1,242 @@
+//! Test helpers: a local stand-in for the advent of code website and temporary working directories.
+#![allow(dead_code)]
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+use std::sync::{Arc, Mutex};
+use std::{env, fs, process, thread};
+
+/// The session cookie that the fake server accepts.
+pub const SESSION: &str = "test-session";
+
+/// A response of the fake server to a submitted answer.
+#[derive(Clone, Debug)]
+pub enum SubmitResponse {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Holds the wait as printed by the website, e.g. `1m 5s`.
+    RateLimited(&'static str),
+    AlreadySolved,
+}
+
+impl SubmitResponse {
+    fn to_html(&self) -> String {
+        let text = match self {
+            SubmitResponse::Correct => "That's the right answer! You are <em>one gold star</em> closer to finding the Chief Historian.".to_string(),
+            SubmitResponse::Wrong => "That's not the right answer. If you're stuck, make sure you're using the full input data.".to_string(),
+            SubmitResponse::TooHigh => "That's not the right answer; your answer is too high. Please wait one minute before trying again.".to_string(),
+            SubmitResponse::TooLow => "That's not the right answer; your answer is too low. Please wait one minute before trying again.".to_string(),
+            SubmitResponse::RateLimited(wait) => format!("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {wait} left to wait."),
+            SubmitResponse::AlreadySolved => "You don't seem to be solving the right level.  Did you already complete it?".to_string(),
+        };
+        format!("<html><body><main>\n<article><p>{text}</p></article>\n</main></body></html>")
+    }
+}
+
+/// A request received by the fake server.
+#[derive(Clone, Debug)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub cookie: Option<String>,
+    pub body: String,
+}
+
+#[derive(Default)]
+struct State {
+    pages: HashMap<String, String>,
+    submit_responses: VecDeque<SubmitResponse>,
+    requests: Vec<Request>,
+}
+
+/// An in-process HTTP server that serves canned puzzle pages and inputs, and answers submissions.
+/// Requests without the [`SESSION`] cookie are rejected like the website does.
+pub struct FakeAoc {
+    url: String,
+    state: Arc<Mutex<State>>,
+}
+
+impl FakeAoc {
+    pub fn start() -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let state = Arc::new(Mutex::new(State::default()));
+
+        let server_state = Arc::clone(&state);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                handle_connection(stream, &server_state);
+            }
+        });
+
+        Self { url, state }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn add_puzzle(&self, year: u16, day: u8, html: &str) {
+        self.add_page(&format!("/{year}/day/{day}"), html);
+    }
+
+    pub fn add_input(&self, year: u16, day: u8, input: &str) {
+        self.add_page(&format!("/{year}/day/{day}/input"), input);
+    }
+
+    /// Queue the response to the next submission. Without queued responses, submissions are answered as wrong.
+    pub fn respond_to_submit(&self, response: SubmitResponse) {
+        self.state
+            .lock()
+            .unwrap()
+            .submit_responses
+            .push_back(response);
+    }
+
+    pub fn requests(&self) -> Vec<Request> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    fn add_page(&self, path: &str, body: &str) {
+        self.state
+            .lock()
+            .unwrap()
+            .pages
+            .insert(path.to_string(), body.to_string());
+    }
+}
+
+fn handle_connection(stream: TcpStream, state: &Mutex<State>) {
+    let mut reader = BufReader::new(&stream);
+
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut cookie = None;
+    let mut content_length = 0;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line).is_err() || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            match name.trim().to_ascii_lowercase().as_str() {
+                "cookie" => cookie = Some(value.trim().to_string()),
+                "content-length" => content_length = value.trim().parse().unwrap_or(0),
+                _ => {}
+            }
+        }
+    }
+
+    let mut body = vec![0; content_length];
+    let _ = reader.read_exact(&mut body);
+    let body = String::from_utf8_lossy(&body).to_string();
+
+    let is_authorized = cookie.as_deref() == Some(&format!("session={SESSION}"));
+
+    let (status, response) = {
+        let mut state = state.lock().unwrap();
+
+        state.requests.push(Request {
+            method: method.clone(),
+            path: path.clone(),
+            cookie,
+            body,
+        });
+
+        if !is_authorized {
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input."
+                    .to_string(),
+            )
+        } else if method == "POST" && path.ends_with("/answer") {
+            let response = state
+                .submit_responses
+                .pop_front()
+                .unwrap_or(SubmitResponse::Wrong);
+            (200, response.to_html())
+        } else if let Some(page) = state.pages.get(&path) {
+            (200, page.clone())
+        } else {
+            (404, "404 Not Found".to_string())
+        }
+    };
+
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        _ => "Not Found",
+    };
+
+    let mut stream = &stream;
+    let _ = write!(
+        stream,
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response}",
+        response.len()
+    );
+    let _ = stream.flush();
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A temporary working directory that is removed on drop.
+pub struct TempDir {
+    path: PathBuf,
+}
+
+impl TempDir {
+    pub fn new(name: &str) -> Self {
+        let path = env::temp_dir().join(format!("advent_of_code-{}-{name}", process::id()));
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        Self { path }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn write(&self, path: &str, contents: &str) {
+        let path = self.path.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    pub fn read(&self, path: &str) -> String {
+        fs::read_to_string(self.path.join(path)).unwrap()
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+/// Run a binary in a working directory, pointed at the fake server.
+pub fn run_bin(bin: &str, dir: &TempDir, server: &FakeAoc, args: &[&str]) -> Output {
+    Command::new(bin)
+        .args(args)
+        .current_dir(dir.path())
+        .env("AOC_SESSION", SESSION)
+        .env("AOC_BASE_URL", server.url())
+        .env("AOC_YEAR", "2024")
+        .env_remove("AOC_REPORT_FILE")
+        .output()
+        .unwrap()
+}
+
+pub fn stdout(output: &Output) -> String {
+    String::from_utf8_lossy(&output.stdout).to_string()
+}